@@ -779,7 +779,15 @@ impl<'a> Builder<'a> {
         }
 
         match mode {
-            Mode::Std | Mode::ToolBootstrap | Mode::ToolStd => {}
+            Mode::Std => {
+                // Tag the functions and vtables of the standard library so that
+                // programs built with `-Z sanitizer=cfi` can call them
+                // indirectly.
+                if stage != 0 {
+                    rustflags.arg("-Zcfi-type-metadata");
+                }
+            }
+            Mode::ToolBootstrap | Mode::ToolStd => {}
             Mode::Rustc | Mode::Codegen | Mode::ToolRustc => {
                 // Build proc macros both for the host and the target
                 if target != compiler.host && cmd != "check" {
@@ -549,6 +549,11 @@ pub fn rustc_cargo_env(builder: &Builder<'_>, cargo: &mut Cargo, target: Interne
         }
         let llvm_config = builder.ensure(native::Llvm { target });
         cargo.env("LLVM_CONFIG", &llvm_config);
+        // `-Z cross-lang-lto` only uses a linker plugin built from this LLVM.
+        if !builder.config.dry_run {
+            let version = output(Command::new(&llvm_config).arg("--version"));
+            cargo.env("CFG_LLVM_VERSION", native::llvm_major_minor_version(&version));
+        }
         let target_config = builder.config.target_config.get(&target);
         if let Some(s) = target_config.and_then(|c| c.llvm_config.as_ref()) {
             cargo.env("CFG_LLVM_ROOT", s);
@@ -766,6 +771,32 @@ impl Step for Assemble {
             builder.copy(&lld_install.join("bin").join(&src_exe), &dst.join(&dst_exe));
         }
 
+        // `-Z split-debuginfo=packed` packages `.dwo` files with LLVM's DWARF
+        // packager, which we ship renamed to `rust-llvm-dwp` for the same
+        // reason as `rust-lld` above.
+        if builder.config.llvm_enabled() && !builder.config.dry_run {
+            let llvm_config = builder.ensure(native::Llvm { target: target_compiler.host });
+            let llvm_bindir = output(Command::new(&llvm_config).arg("--bindir"));
+            let src = Path::new(llvm_bindir.trim()).join(exe("llvm-dwp", &target_compiler.host));
+            if src.exists() {
+                let dst = libdir.parent().unwrap().join("bin");
+                t!(fs::create_dir_all(&dst));
+                builder.copy(&src, &dst.join(exe("rust-llvm-dwp", &target_compiler.host)));
+            }
+        }
+
+        // `-Z cross-lang-lto` looks for the linker plugin built along with LLVM
+        // next to the host libraries. It's only built if `binutils-incdir` is
+        // set.
+        if builder.config.llvm_enabled() && !builder.config.dry_run && host.contains("linux") {
+            let llvm_config = builder.ensure(native::Llvm { target: host });
+            let llvm_libdir = output(Command::new(&llvm_config).arg("--libdir"));
+            let src = Path::new(llvm_libdir.trim()).join("LLVMgold.so");
+            if src.exists() {
+                builder.copy(&src, &libdir.join(native::llvm_gold_plugin_name(&llvm_config)));
+            }
+        }
+
         // Ensure that `libLLVM.so` ends up in the newly build compiler directory,
         // so that it can be found when the newly built `rustc` is run.
         dist::maybe_install_llvm_dylib(builder, target_compiler.host, &sysroot);
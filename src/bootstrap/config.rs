@@ -82,6 +82,7 @@ pub struct Config {
     pub llvm_version_suffix: Option<String>,
     pub llvm_use_linker: Option<String>,
     pub llvm_allow_old_toolchain: Option<bool>,
+    pub llvm_binutils_incdir: Option<String>,
 
     pub lld_enabled: bool,
     pub lldb_enabled: bool,
@@ -266,6 +267,7 @@ struct Llvm {
     use_libcxx: Option<bool>,
     use_linker: Option<String>,
     allow_old_toolchain: Option<bool>,
+    binutils_incdir: Option<String>,
 }
 
 #[derive(Deserialize, Default, Clone)]
@@ -533,6 +535,7 @@ impl Config {
             set(&mut config.llvm_use_libcxx, llvm.use_libcxx);
             config.llvm_use_linker = llvm.use_linker.clone();
             config.llvm_allow_old_toolchain = llvm.allow_old_toolchain.clone();
+            config.llvm_binutils_incdir = llvm.binutils_incdir.clone();
         }
 
         if let Some(ref rust) = toml.rust {
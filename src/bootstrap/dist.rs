@@ -20,6 +20,7 @@ use crate::builder::{Builder, RunConfig, ShouldRun, Step};
 use crate::cache::{Interned, INTERNER};
 use crate::channel;
 use crate::compile;
+use crate::native;
 use crate::tool::{self, Tool};
 use crate::util::{exe, is_dylib, timeit};
 use crate::{Compiler, Mode, LLVM_TOOLS};
@@ -521,6 +522,28 @@ impl Step for Rustc {
                 builder.copy(&src, &dst);
             }
 
+            // Copy over the DWARF packager if it's there
+            let exe = exe("rust-llvm-dwp", &compiler.host);
+            let src =
+                builder.sysroot_libdir(compiler, host).parent().unwrap().join("bin").join(&exe);
+            if src.exists() {
+                let dst = image.join("lib/rustlib").join(&*host).join("bin").join(&exe);
+                t!(fs::create_dir_all(&dst.parent().unwrap()));
+                builder.copy(&src, &dst);
+            }
+
+            // Copy over the linker plugin for `-Z cross-lang-lto` if it's there
+            if builder.config.llvm_enabled() && !builder.config.dry_run && host.contains("linux") {
+                let llvm_config = builder.ensure(native::Llvm { target: host });
+                let name = native::llvm_gold_plugin_name(&llvm_config);
+                let src = builder.sysroot_libdir(compiler, host).join(&name);
+                if src.exists() {
+                    let dst = image.join("lib/rustlib").join(&*host).join("lib").join(&name);
+                    t!(fs::create_dir_all(&dst.parent().unwrap()));
+                    builder.copy(&src, &dst);
+                }
+            }
+
             // Man pages
             t!(fs::create_dir_all(image.join("share/man/man1")));
             let man_src = builder.src.join("src/doc/man");
@@ -985,6 +1008,7 @@ impl Step for Src {
             "src/libpanic_abort",
             "src/libpanic_unwind",
             "src/librustc_asan",
+            "src/librustc_hwasan",
             "src/librustc_lsan",
             "src/librustc_msan",
             "src/librustc_tsan",
@@ -254,6 +254,10 @@ impl Step for Llvm {
             cfg.define("LLVM_USE_LINKER", linker);
         }
 
+        if let Some(ref incdir) = builder.config.llvm_binutils_incdir {
+            cfg.define("LLVM_BINUTILS_INCDIR", incdir);
+        }
+
         if let Some(true) = builder.config.llvm_allow_old_toolchain {
             cfg.define("LLVM_TEMPORARILY_ALLOW_OLD_TOOLCHAIN", "YES");
         }
@@ -281,6 +285,21 @@ impl Step for Llvm {
     }
 }
 
+/// Returns the name the LLVM linker plugin built along with the LLVM of
+/// `llvm_config` is shipped under, which contains the `major.minor` version
+/// of that LLVM so that rustc can tell whether the plugin understands the
+/// bitcode it emits.
+pub fn llvm_gold_plugin_name(llvm_config: &Path) -> String {
+    let version = output(Command::new(llvm_config).arg("--version"));
+    format!("LLVMgold-{}.so", llvm_major_minor_version(&version))
+}
+
+/// Returns the `major.minor` part of the output of `llvm-config --version`,
+/// as printed by `rustc --version --verbose`.
+pub fn llvm_major_minor_version(version: &str) -> String {
+    version.trim().split('.').take(2).collect::<Vec<_>>().join(".")
+}
+
 fn check_llvm_version(builder: &Builder<'_>, llvm_config: &Path) {
     if !builder.config.llvm_version_check {
         return;
@@ -1550,6 +1550,7 @@ impl Step for CrateNotDefault {
 
     fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
         run.path("src/librustc_asan")
+            .path("src/librustc_hwasan")
             .path("src/librustc_lsan")
             .path("src/librustc_msan")
             .path("src/librustc_tsan")
@@ -1567,6 +1568,7 @@ impl Step for CrateNotDefault {
             test_kind,
             krate: match run.path {
                 _ if run.path.ends_with("src/librustc_asan") => "rustc_asan",
+                _ if run.path.ends_with("src/librustc_hwasan") => "rustc_hwasan",
                 _ if run.path.ends_with("src/librustc_lsan") => "rustc_lsan",
                 _ if run.path.ends_with("src/librustc_msan") => "rustc_msan",
                 _ if run.path.ends_with("src/librustc_tsan") => "rustc_tsan",
@@ -266,6 +266,9 @@ pub fn sanitizer_lib_boilerplate(
         "x86_64-unknown-linux-gnu" => {
             (format!("clang_rt.{}-x86_64", sanitizer_name), "build/lib/linux", false)
         }
+        "aarch64-unknown-linux-gnu" => {
+            (format!("clang_rt.{}-aarch64", sanitizer_name), "build/lib/linux", false)
+        }
         "x86_64-apple-darwin" => {
             (format!("clang_rt.{}_osx_dynamic", sanitizer_name), "build/lib/darwin", true)
         }
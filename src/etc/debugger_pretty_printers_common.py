@@ -40,6 +40,14 @@ TYPE_KIND_OS_STRING         = 18
 TYPE_KIND_STD_VECDEQUE      = 19
 TYPE_KIND_STD_BTREESET      = 20
 TYPE_KIND_STD_BTREEMAP      = 21
+TYPE_KIND_STD_HASHMAP       = 22
+TYPE_KIND_STD_HASHSET       = 23
+TYPE_KIND_STD_RC            = 24
+TYPE_KIND_STD_ARC           = 25
+TYPE_KIND_STD_CELL          = 26
+TYPE_KIND_STD_REF           = 27
+TYPE_KIND_STD_REF_MUT       = 28
+TYPE_KIND_STD_REF_CELL      = 29
 
 ENCODED_ENUM_PREFIX = "RUST$ENCODED$ENUM$"
 ENUM_DISR_FIELD_NAME = "RUST$ENUM$DISR"
@@ -69,6 +77,24 @@ STD_BTREESET_FIELD_NAMES = ["map"]
 # std::collections::BTreeMap<> related constants
 STD_BTREEMAP_FIELD_NAMES = ["root", "length"]
 
+# std::collections::HashMap<> related constants
+STD_HASHMAP_FIELD_NAMES = ["base"]
+
+# std::collections::HashSet<> related constants
+STD_HASHSET_FIELD_NAMES = ["map"]
+
+# std::rc::Rc<> and std::sync::Arc<> related constants
+STD_RC_FIELD_NAMES = ["ptr", "phantom"]
+
+# std::cell::Cell<> related constants
+STD_CELL_FIELD_NAMES = ["value"]
+
+# std::cell::Ref<> and std::cell::RefMut<> related constants
+STD_REF_FIELD_NAMES = ["value", "borrow"]
+
+# std::cell::RefCell<> related constants
+STD_REF_CELL_FIELD_NAMES = ["borrow", "value"]
+
 # std::String related constants
 STD_STRING_FIELD_NAMES = ["vec"]
 
@@ -124,6 +150,10 @@ class Type(object):
         """
         raise NotImplementedError("Override this method")
 
+    def get_size_in_bytes(self):
+        """Returns the size of values of the wrapped type-object in bytes."""
+        raise NotImplementedError("Override this method")
+
     def get_type_kind(self):
         """This method returns the TYPE_KIND_* value for this type-object."""
         if self.__type_kind is None:
@@ -183,6 +213,46 @@ class Type(object):
                 self.__conforms_to_field_layout(STD_BTREEMAP_FIELD_NAMES)):
             return TYPE_KIND_STD_BTREEMAP
 
+        # STD COLLECTION HASHMAP
+        if (unqualified_type_name.startswith("HashMap<") and
+                self.__conforms_to_field_layout(STD_HASHMAP_FIELD_NAMES)):
+            return TYPE_KIND_STD_HASHMAP
+
+        # STD COLLECTION HASHSET
+        if (unqualified_type_name.startswith("HashSet<") and
+                self.__conforms_to_field_layout(STD_HASHSET_FIELD_NAMES)):
+            return TYPE_KIND_STD_HASHSET
+
+        # STD RC
+        if (unqualified_type_name.startswith("Rc<") and
+                self.__conforms_to_field_layout(STD_RC_FIELD_NAMES)):
+            return TYPE_KIND_STD_RC
+
+        # STD ARC
+        if (unqualified_type_name.startswith("Arc<") and
+                self.__conforms_to_field_layout(STD_RC_FIELD_NAMES)):
+            return TYPE_KIND_STD_ARC
+
+        # STD CELL
+        if (unqualified_type_name.startswith("Cell<") and
+                self.__conforms_to_field_layout(STD_CELL_FIELD_NAMES)):
+            return TYPE_KIND_STD_CELL
+
+        # STD REF
+        if (unqualified_type_name.startswith("Ref<") and
+                self.__conforms_to_field_layout(STD_REF_FIELD_NAMES)):
+            return TYPE_KIND_STD_REF
+
+        # STD REF MUT
+        if (unqualified_type_name.startswith("RefMut<") and
+                self.__conforms_to_field_layout(STD_REF_FIELD_NAMES)):
+            return TYPE_KIND_STD_REF_MUT
+
+        # STD REF CELL
+        if (unqualified_type_name.startswith("RefCell<") and
+                self.__conforms_to_field_layout(STD_REF_CELL_FIELD_NAMES)):
+            return TYPE_KIND_STD_REF_CELL
+
         # STD STRING
         if (unqualified_type_name.startswith("String") and
             self.__conforms_to_field_layout(STD_STRING_FIELD_NAMES)):
@@ -278,6 +348,25 @@ class Value(object):
         """
         raise NotImplementedError("Override this method")
 
+    def as_signed_integer(self):
+        """Like `as_integer`, but interprets the value as two's complement."""
+        value = self.as_integer()
+        bits = self.type.get_size_in_bytes() * 8
+        if value >= 1 << (bits - 1):
+            value -= 1 << bits
+        return value
+
+    def dereference(self):
+        """Returns the value this pointer value points to."""
+        raise NotImplementedError("Override this method")
+
+    def read_bytes(self, count):
+        """
+        Returns a `bytearray` with the `count` bytes of memory this pointer
+        value points to.
+        """
+        raise NotImplementedError("Override this method")
+
     def get_wrapped_value(self):
         """
         Returns the debugger-specific value-object wrapped by this object. This
@@ -363,6 +452,85 @@ def extract_tail_head_ptr_and_cap_from_std_vecdeque(vec_val):
     assert data_ptr.type.get_dwarf_type_kind() == DWARF_TYPE_CODE_PTR
     return (tail, head, data_ptr, capacity)
 
+def extract_raw_table_from_std_hashmap(map_val):
+    """
+    Returns the `hashbrown::raw::RawTable` behind a `HashMap` or `HashSet`,
+    whose buckets hold `(K, V)` tuples, or `(T, ())` tuples for sets.
+    """
+    type_kind = map_val.type.get_type_kind()
+    assert (type_kind == TYPE_KIND_STD_HASHMAP or
+            type_kind == TYPE_KIND_STD_HASHSET)
+    if type_kind == TYPE_KIND_STD_HASHSET:
+        map_val = map_val.get_child_at_index(0)
+    base = map_val.get_child_at_index(0)
+    return get_child_by_name(base, "table")
+
+
+def extract_occupied_buckets_from_raw_table(table_val):
+    """
+    Returns the item count of a `RawTable` and the indices of its occupied
+    buckets. A bucket is occupied if the top bit of its control byte is clear.
+    """
+    items = get_child_by_name(table_val, "items").as_integer()
+    bucket_mask = get_child_by_name(table_val, "bucket_mask").as_integer()
+    ctrl_ptr = get_child_by_name(table_val, "ctrl").get_child_at_index(0)
+    ctrl_bytes = ctrl_ptr.read_bytes(bucket_mask + 1)
+    occupied = [i for i in range(bucket_mask + 1) if ctrl_bytes[i] & 0x80 == 0]
+    return (items, occupied[:items])
+
+
+def extract_inner_and_counts_from_std_rc(rc_val):
+    """
+    Returns the `RcBox`/`ArcInner` an `Rc`/`Arc` points to along with its
+    strong and weak counts. The implicit weak reference held collectively by
+    the strong references is not included in the weak count.
+    """
+    type_kind = rc_val.type.get_type_kind()
+    assert (type_kind == TYPE_KIND_STD_RC or
+            type_kind == TYPE_KIND_STD_ARC)
+    ptr = rc_val.get_child_at_index(0).get_child_at_index(0)
+    assert ptr.type.get_dwarf_type_kind() == DWARF_TYPE_CODE_PTR
+    inner = ptr.dereference()
+
+    # `Cell<usize>` for `Rc` and `AtomicUsize` for `Arc`, both wrapping an
+    # `UnsafeCell<usize>`.
+    strong = inner.get_child_at_index(0).get_child_at_index(0).get_child_at_index(0)
+    weak = inner.get_child_at_index(1).get_child_at_index(0).get_child_at_index(0)
+    return (inner, strong.as_integer(), weak.as_integer() - 1)
+
+
+def extract_value_from_std_cell(cell_val):
+    assert cell_val.type.get_type_kind() == TYPE_KIND_STD_CELL
+    return cell_val.get_child_at_index(0).get_child_at_index(0)
+
+
+def extract_value_and_borrow_from_std_ref_cell(ref_cell_val):
+    """
+    Returns the value inside a `RefCell`, `Ref` or `RefMut` and the borrow
+    flag of the `RefCell`. The flag counts the shared borrows if it is
+    positive and the mutable borrows if it is negative.
+    """
+    type_kind = ref_cell_val.type.get_type_kind()
+    if type_kind == TYPE_KIND_STD_REF_CELL:
+        borrow_cell = ref_cell_val.get_child_at_index(0)
+        value = ref_cell_val.get_child_at_index(1).get_child_at_index(0)
+    else:
+        assert (type_kind == TYPE_KIND_STD_REF or
+                type_kind == TYPE_KIND_STD_REF_MUT)
+        value = ref_cell_val.get_child_at_index(0).dereference()
+        borrow_cell = ref_cell_val.get_child_at_index(1).get_child_at_index(0).dereference()
+    borrow = borrow_cell.get_child_at_index(0).get_child_at_index(0).as_signed_integer()
+    return (value, borrow)
+
+
+def get_child_by_name(val, name):
+    fields = val.type.get_fields()
+    for index in range(0, len(fields)):
+        if fields[index].name == name:
+            return val.get_child_at_index(index)
+    raise KeyError(name)
+
+
 def extract_length_and_ptr_from_slice(slice_val):
     assert (slice_val.type.get_type_kind() == TYPE_KIND_SLICE or
             slice_val.type.get_type_kind() == TYPE_KIND_STR_SLICE)
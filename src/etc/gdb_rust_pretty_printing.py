@@ -63,6 +63,9 @@ class GdbType(rustpp.Type):
     def get_wrapped_value(self):
         return self.ty
 
+    def get_size_in_bytes(self):
+        return self.ty.sizeof
+
 
 class GdbValue(rustpp.Value):
     def __init__(self, gdb_val):
@@ -84,6 +87,13 @@ class GdbValue(rustpp.Value):
             return int(as_str, 0)
         return int(self.gdb_val)
 
+    def dereference(self):
+        return GdbValue(self.gdb_val.dereference())
+
+    def read_bytes(self, count):
+        memory = gdb.selected_inferior().read_memory(self.as_integer(), count)
+        return bytearray(memory)
+
     def get_wrapped_value(self):
         return self.gdb_val
 
@@ -123,6 +133,24 @@ def rust_pretty_printer_lookup_function(gdb_val):
     if type_kind == rustpp.TYPE_KIND_OS_STRING:
         return RustOsStringPrinter(val)
 
+    if type_kind == rustpp.TYPE_KIND_STD_HASHMAP:
+        return RustStdHashMapPrinter(val, show_values = True)
+
+    if type_kind == rustpp.TYPE_KIND_STD_HASHSET:
+        return RustStdHashMapPrinter(val, show_values = False)
+
+    if (type_kind == rustpp.TYPE_KIND_STD_RC or
+        type_kind == rustpp.TYPE_KIND_STD_ARC):
+        return RustStdRcPrinter(val)
+
+    if type_kind == rustpp.TYPE_KIND_STD_CELL:
+        return RustStdCellPrinter(val)
+
+    if (type_kind == rustpp.TYPE_KIND_STD_REF_CELL or
+        type_kind == rustpp.TYPE_KIND_STD_REF or
+        type_kind == rustpp.TYPE_KIND_STD_REF_MUT):
+        return RustStdRefCellPrinter(val)
+
     # Checks after this point should only be for "compiler" types --
     # things that gdb's Rust language support knows about.
     if rust_enabled:
@@ -398,6 +426,80 @@ class RustStdBTreeMapPrinter(object):
             i = i + 1
 
 
+class RustStdHashMapPrinter(object):
+    def __init__(self, val, show_values):
+        self.__val = val
+        self.__show_values = show_values
+
+    def display_hint(self):
+        if self.__show_values:
+            return "map"
+        else:
+            return "array"
+
+    def to_string(self):
+        table = rustpp.extract_raw_table_from_std_hashmap(self.__val)
+        length = rustpp.get_child_by_name(table, "items").as_integer()
+        return (self.__val.type.get_unqualified_type_name() +
+                ("(len: %i)" % length))
+
+    def children(self):
+        table = rustpp.extract_raw_table_from_std_hashmap(self.__val)
+        (length, occupied) = rustpp.extract_occupied_buckets_from_raw_table(table)
+        data_ptr = rustpp.get_child_by_name(table, "data").get_child_at_index(0)
+        gdb_ptr = data_ptr.get_wrapped_value()
+        for (i, index) in enumerate(occupied):
+            element = (gdb_ptr + index).dereference()
+            if self.__show_values:
+                yield (str(i), element["__0"])
+                yield (str(i), element["__1"])
+            else:
+                yield (str(i), element["__0"])
+
+
+class RustStdRcPrinter(object):
+    def __init__(self, val):
+        self.__val = val
+
+    def to_string(self):
+        (inner, strong, weak) = rustpp.extract_inner_and_counts_from_std_rc(self.__val)
+        return (self.__val.type.get_unqualified_type_name() +
+                ("(strong: %i, weak: %i)" % (strong, weak)))
+
+    def children(self):
+        (inner, strong, weak) = rustpp.extract_inner_and_counts_from_std_rc(self.__val)
+        yield ("value", inner.get_child_at_index(2).get_wrapped_value())
+
+
+class RustStdCellPrinter(object):
+    def __init__(self, val):
+        self.__val = val
+
+    def to_string(self):
+        return self.__val.type.get_unqualified_type_name()
+
+    def children(self):
+        value = rustpp.extract_value_from_std_cell(self.__val)
+        yield ("value", value.get_wrapped_value())
+
+
+class RustStdRefCellPrinter(object):
+    def __init__(self, val):
+        self.__val = val
+
+    def to_string(self):
+        (value, borrow) = rustpp.extract_value_and_borrow_from_std_ref_cell(self.__val)
+        if borrow >= 0:
+            state = "(borrow: %i)" % borrow
+        else:
+            state = "(borrow_mut: %i)" % -borrow
+        return self.__val.type.get_unqualified_type_name() + state
+
+    def children(self):
+        (value, borrow) = rustpp.extract_value_and_borrow_from_std_ref_cell(self.__val)
+        yield ("value", value.get_wrapped_value())
+
+
 class RustStdStringPrinter(object):
     def __init__(self, val):
         self.__val = val
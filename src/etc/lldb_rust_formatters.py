@@ -51,6 +51,9 @@ class LldbType(rustpp.Type):
     def get_wrapped_value(self):
         return self.ty
 
+    def get_size_in_bytes(self):
+        return self.ty.GetByteSize()
+
 
 class LldbValue(rustpp.Value):
     def __init__(self, lldb_val):
@@ -71,6 +74,19 @@ class LldbValue(rustpp.Value):
     def as_integer(self):
         return self.lldb_val.GetValueAsUnsigned()
 
+    def dereference(self):
+        return LldbValue(self.lldb_val.Dereference())
+
+    def read_bytes(self, count):
+        if count == 0:
+            return bytearray()
+        error = lldb.SBError()
+        process = self.lldb_val.GetProcess()
+        data = process.ReadMemory(self.as_integer(), count, error)
+        if not error.Success():
+            raise RuntimeError(error.GetCString())
+        return bytearray(data)
+
     def get_wrapped_value(self):
         return self.lldb_val
 
@@ -107,6 +123,36 @@ def print_val(lldb_val, internal_dict):
     if type_kind == rustpp.TYPE_KIND_STD_STRING:
         return print_std_string_val(val, internal_dict)
 
+    if type_kind == rustpp.TYPE_KIND_OS_STRING:
+        return print_os_string_val(val, internal_dict)
+
+    if type_kind == rustpp.TYPE_KIND_STD_VECDEQUE:
+        return print_std_vecdeque_val(val, internal_dict)
+
+    if type_kind == rustpp.TYPE_KIND_STD_BTREEMAP:
+        return print_std_btreemap_val(val, internal_dict)
+
+    if type_kind == rustpp.TYPE_KIND_STD_BTREESET:
+        return print_std_btreeset_val(val, internal_dict)
+
+    if type_kind == rustpp.TYPE_KIND_STD_HASHMAP:
+        return print_std_hashmap_val(val, internal_dict, show_values = True)
+
+    if type_kind == rustpp.TYPE_KIND_STD_HASHSET:
+        return print_std_hashmap_val(val, internal_dict, show_values = False)
+
+    if (type_kind == rustpp.TYPE_KIND_STD_RC or
+        type_kind == rustpp.TYPE_KIND_STD_ARC):
+        return print_std_rc_val(val, internal_dict)
+
+    if type_kind == rustpp.TYPE_KIND_STD_CELL:
+        return print_std_cell_val(val, internal_dict)
+
+    if (type_kind == rustpp.TYPE_KIND_STD_REF_CELL or
+        type_kind == rustpp.TYPE_KIND_STD_REF or
+        type_kind == rustpp.TYPE_KIND_STD_REF_MUT):
+        return print_std_ref_cell_val(val, internal_dict)
+
     if type_kind == rustpp.TYPE_KIND_TUPLE:
         return print_struct_val(val,
                                 internal_dict,
@@ -262,10 +308,134 @@ def print_std_string_val(val, internal_dict):
     (length, data_ptr, cap) = rustpp.extract_length_ptr_and_cap_from_std_vec(vec)
     return read_utf8_string(data_ptr, length)
 
+def print_os_string_val(val, internal_dict):
+    buf = val.get_child_at_index(0)
+    vec = buf.get_child_at_index(0)
+    if vec.type.get_unqualified_type_name() == "Wtf8Buf":
+        vec = vec.get_child_at_index(0)
+    (length, data_ptr, cap) = rustpp.extract_length_ptr_and_cap_from_std_vec(vec)
+    return read_utf8_string(data_ptr, length)
+
+def print_std_vecdeque_val(val, internal_dict):
+    (tail, head, data_ptr, cap) = rustpp.extract_tail_head_ptr_and_cap_from_std_vecdeque(val)
+    if head >= tail:
+        size = head - tail
+    else:
+        size = cap + head - tail
+    indices = [(tail + i) % cap for i in range(size)]
+    elements = [print_element_at_index(val.get_wrapped_value().GetName(),
+                                       data_ptr,
+                                       index,
+                                       internal_dict)
+                for index in indices]
+    return "VecDeque[%s]" % ", ".join(elements)
+
+def print_std_btreemap_val(val, internal_dict):
+    root = val.get_wrapped_value().GetChildMemberWithName("root")
+    entries = ["%s: %s" % (print_val(key, internal_dict), print_val(value, internal_dict))
+               for (key, value) in children_of_btree_node(root, want_values = True)]
+    return "{%s}" % ", ".join(entries)
+
+def print_std_btreeset_val(val, internal_dict):
+    root = val.get_wrapped_value().GetChildMemberWithName("map").GetChildMemberWithName("root")
+    entries = [print_val(key, internal_dict)
+               for key in children_of_btree_node(root, want_values = False)]
+    return "{%s}" % ", ".join(entries)
+
+def print_std_hashmap_val(val, internal_dict, show_values):
+    table = rustpp.extract_raw_table_from_std_hashmap(val)
+    (length, occupied) = rustpp.extract_occupied_buckets_from_raw_table(table)
+    data_ptr = rustpp.get_child_by_name(table, "data").get_child_at_index(0)
+    array_name = val.get_wrapped_value().GetName()
+
+    def render_entry(index):
+        element = element_at_index(array_name, data_ptr, index)
+        key = print_val(element.GetChildMemberWithName("__0"), internal_dict)
+        if not show_values:
+            return key
+        value = print_val(element.GetChildMemberWithName("__1"), internal_dict)
+        return "%s: %s" % (key, value)
+
+    return "{%s}" % ", ".join([render_entry(index) for index in occupied])
+
+def print_std_rc_val(val, internal_dict):
+    (inner, strong, weak) = rustpp.extract_inner_and_counts_from_std_rc(val)
+    value = inner.get_child_at_index(2).get_wrapped_value()
+    return "%s(strong: %i, weak: %i) %s" % (val.type.get_unqualified_type_name(),
+                                            strong,
+                                            weak,
+                                            print_val(value, internal_dict))
+
+def print_std_cell_val(val, internal_dict):
+    value = rustpp.extract_value_from_std_cell(val)
+    return "%s { value: %s }" % (val.type.get_unqualified_type_name(),
+                                 print_val(value.get_wrapped_value(), internal_dict))
+
+def print_std_ref_cell_val(val, internal_dict):
+    (value, borrow) = rustpp.extract_value_and_borrow_from_std_ref_cell(val)
+    if borrow >= 0:
+        state = "borrow: %i" % borrow
+    else:
+        state = "borrow_mut: %i" % -borrow
+    return "%s(%s) { value: %s }" % (val.type.get_unqualified_type_name(),
+                                     state,
+                                     print_val(value.get_wrapped_value(), internal_dict))
+
 #=--------------------------------------------------------------------------------------------------
 # Helper Functions
 #=--------------------------------------------------------------------------------------------------
 
+def element_at_index(array_name, data_ptr_val, index):
+    """Returns the value at `index` in the array pointed to by data_ptr_val."""
+    element_type = data_ptr_val.type.get_wrapped_value().GetPointeeType()
+    address = data_ptr_val.as_integer() + index * element_type.GetByteSize()
+    return data_ptr_val.get_wrapped_value().CreateValueFromAddress(array_name + ("[%s]" % index),
+                                                                   address,
+                                                                   element_type)
+
+def print_element_at_index(array_name, data_ptr_val, index, internal_dict):
+    return print_val(element_at_index(array_name, data_ptr_val, index), internal_dict)
+
+def children_of_btree_node(root, want_values):
+    """
+    Yields each key (and optionally value) of a B-tree, given its `node::Root`.
+    Internal nodes are only referred to as `LeafNode` pointers, so we look up
+    the `InternalNode` type to read their edges.
+    """
+    def children_of_node(boxed_node, height):
+        node_ptr = boxed_node.GetChildMemberWithName("ptr").GetChildMemberWithName("pointer")
+        if height > 0:
+            type_name = node_ptr.GetType().GetPointeeType().GetName()
+            type_name = type_name.replace("LeafNode", "InternalNode")
+            node_type = node_ptr.GetTarget().FindFirstType(type_name)
+            node = node_ptr.Cast(node_type.GetPointerType()).Dereference()
+            leaf = node.GetChildMemberWithName("data")
+            edges = node.GetChildMemberWithName("edges")
+        else:
+            leaf = node_ptr.Dereference()
+        keys = leaf.GetChildMemberWithName("keys")
+        values = leaf.GetChildMemberWithName("vals")
+        length = leaf.GetChildMemberWithName("len").GetValueAsUnsigned()
+        for i in range(length + 1):
+            if height > 0:
+                edge = edges.GetChildAtIndex(i)
+                child = edge.GetChildMemberWithName("value").GetChildMemberWithName("value")
+                for entry in children_of_node(child, height - 1):
+                    yield entry
+            if i < length:
+                key = keys.GetChildAtIndex(i)
+                key = key.GetChildMemberWithName("value").GetChildMemberWithName("value")
+                if want_values:
+                    value = values.GetChildAtIndex(i)
+                    value = value.GetChildMemberWithName("value").GetChildMemberWithName("value")
+                    yield (key, value)
+                else:
+                    yield key
+
+    height = root.GetChildMemberWithName("height").GetValueAsUnsigned()
+    return children_of_node(root.GetChildMemberWithName("node"), height)
+
+
 def print_array_of_values(array_name, data_ptr_val, length, internal_dict):
     """Prints a contiguous memory range, interpreting it as values of the
        pointee-type of data_ptr_val."""
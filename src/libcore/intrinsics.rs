@@ -1404,6 +1404,17 @@ extern "rust-intrinsic" {
     ///
     /// Perma-unstable: do not use
     pub fn miri_start_panic(data: *mut (dyn crate::any::Any + crate::marker::Send)) -> ();
+
+    /// Internal placeholder for the code coverage counters of
+    /// `-Z instrument-coverage`, injected into function bodies by the compiler:
+    /// increments the counter `index` of the enclosing function, out of its
+    /// `num_counters` counters. The region of code the counter covers is the
+    /// span of the call.
+    ///
+    /// Perma-unstable: do not use
+    #[cfg(not(bootstrap))]
+    #[lang = "count_code_region"]
+    pub fn count_code_region(function_source_hash: u64, num_counters: u32, index: u32);
 }
 
 // Some functions are defined here because they accidentally got made
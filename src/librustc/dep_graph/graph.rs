@@ -922,6 +922,7 @@ pub enum WorkProductFileKind {
     Object,
     Bytecode,
     BytecodeCompressed,
+    DwarfObject,
 }
 
 #[derive(Clone)]
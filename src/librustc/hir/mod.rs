@@ -2782,6 +2782,21 @@ bitflags! {
         const FFI_RETURNS_TWICE         = 1 << 10;
         /// `#[track_caller]`: allow access to the caller location
         const TRACK_CALLER              = 1 << 11;
+        /// `#[no_sanitize(address)]`: disables address sanitizer
+        /// instrumentation, including that of the kernel address sanitizer.
+        const NO_SANITIZE_ADDRESS       = 1 << 12;
+        /// `#[no_sanitize(memory)]`: disables memory sanitizer instrumentation
+        const NO_SANITIZE_MEMORY        = 1 << 13;
+        /// `#[no_sanitize(thread)]`: disables thread sanitizer instrumentation
+        const NO_SANITIZE_THREAD        = 1 << 14;
+        /// `#[no_sanitize(hwaddress)]`: disables hardware-assisted address
+        /// sanitizer instrumentation
+        const NO_SANITIZE_HWADDRESS     = 1 << 15;
+        /// All `#[no_sanitize(...)]` attributes.
+        const NO_SANITIZE_ANY = Self::NO_SANITIZE_ADDRESS.bits
+            | Self::NO_SANITIZE_MEMORY.bits
+            | Self::NO_SANITIZE_THREAD.bits
+            | Self::NO_SANITIZE_HWADDRESS.bits;
     }
 }
 
@@ -108,6 +108,18 @@ pub struct NativeLibrary {
     pub cfg: Option<ast::MetaItem>,
     pub foreign_module: Option<DefId>,
     pub wasm_import_module: Option<Symbol>,
+    /// The `#[link]` attribute requesting the library, or `None` if it was
+    /// requested with `-l` on the command line.
+    pub span: Option<Span>,
+}
+
+/// A debugger script embedded into the output with `#[debugger_visualizer]`.
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable, HashStable)]
+pub struct DebuggerVisualizerFile {
+    /// The path of the script as written in the attribute.
+    pub name: String,
+    /// The contents of the script.
+    pub src: Vec<u8>,
 }
 
 #[derive(Clone, RustcEncodable, RustcDecodable, HashStable)]
@@ -114,12 +114,42 @@ struct LanguageItemCollector<'tcx> {
 
 impl ItemLikeVisitor<'v> for LanguageItemCollector<'tcx> {
     fn visit_item(&mut self, item: &hir::Item<'_>) {
-        if let Some((value, span)) = extract(&item.attrs) {
-            let actual_target = Target::from_item(item);
+        self.check_for_lang(Target::from_item(item), item.hir_id, &item.attrs);
+
+        // Intrinsics that the compiler calls on its own are declared in
+        // `extern "rust-intrinsic"` blocks.
+        if let hir::ItemKind::ForeignMod(ref foreign_mod) = item.kind {
+            for foreign_item in foreign_mod.items {
+                self.check_for_lang(
+                    Target::from_foreign_item(foreign_item),
+                    foreign_item.hir_id,
+                    &foreign_item.attrs,
+                );
+            }
+        }
+    }
+
+    fn visit_trait_item(&mut self, _trait_item: &hir::TraitItem<'_>) {
+        // At present, lang items are always items, not trait items.
+    }
+
+    fn visit_impl_item(&mut self, _impl_item: &hir::ImplItem<'_>) {
+        // At present, lang items are always items, not impl items.
+    }
+}
+
+impl LanguageItemCollector<'tcx> {
+    fn check_for_lang(
+        &mut self,
+        actual_target: Target,
+        hir_id: hir::HirId,
+        attrs: &[ast::Attribute],
+    ) {
+        if let Some((value, span)) = extract(attrs) {
             match self.item_refs.get(&*value.as_str()).cloned() {
                 // Known lang item with attribute on correct target.
                 Some((item_index, expected_target)) if actual_target == expected_target => {
-                    let def_id = self.tcx.hir().local_def_id(item.hir_id);
+                    let def_id = self.tcx.hir().local_def_id(hir_id);
                     self.collect_item(item_index, def_id);
                 },
                 // Known lang item with attribute on incorrect target.
@@ -151,16 +181,6 @@ impl ItemLikeVisitor<'v> for LanguageItemCollector<'tcx> {
         }
     }
 
-    fn visit_trait_item(&mut self, _trait_item: &hir::TraitItem<'_>) {
-        // At present, lang items are always items, not trait items.
-    }
-
-    fn visit_impl_item(&mut self, _impl_item: &hir::ImplItem<'_>) {
-        // At present, lang items are always items, not impl items.
-    }
-}
-
-impl LanguageItemCollector<'tcx> {
     fn new(tcx: TyCtxt<'tcx>) -> LanguageItemCollector<'tcx> {
         let mut item_refs = FxHashMap::default();
 
@@ -396,6 +416,8 @@ language_item_table! {
 
     MaybeUninitLangItem,         "maybe_uninit",       maybe_uninit,            Target::Union;
 
+    CountCodeRegionFnLangItem,   "count_code_region",  count_code_region_fn,    Target::ForeignFn;
+
     // Align offset for stride != 1; must not panic.
     AlignOffsetLangItem,         "align_offset",       align_offset_fn,         Target::Fn;
 
@@ -2685,10 +2685,19 @@ pub struct GeneratorLayout<'tcx> {
     /// The type of every local stored inside the generator.
     pub field_tys: IndexVec<GeneratorSavedLocal, Ty<'tcx>>,
 
+    /// The name of every local above that is a user variable; this is only
+    /// used by `-Z print-type-sizes`.
+    pub field_names: IndexVec<GeneratorSavedLocal, Option<Name>>,
+
     /// Which of the above fields are in each variant. Note that one field may
     /// be stored in multiple variants.
     pub variant_fields: IndexVec<VariantIdx, IndexVec<Field, GeneratorSavedLocal>>,
 
+    /// The source location each variant corresponds to. For suspend states
+    /// this is the `yield` (or `.await`) the generator is suspended at; this
+    /// is only used for debuginfo.
+    pub variant_source_info: IndexVec<VariantIdx, SourceInfo>,
+
     /// Which saved locals are storage-live at the same time. Locals that do not
     /// have conflicts with each other are allowed to overlap in the computed
     /// layout.
@@ -74,6 +74,14 @@ rustc_queries! {
             desc { "looking up the native libraries of a linked crate" }
         }
 
+        /// The debugger scripts a crate embeds with `#[debugger_visualizer]`.
+        /// These are read from disk for the local crate, so the query is never
+        /// assumed to be unchanged.
+        query debugger_visualizers(_: CrateNum) -> Lrc<Vec<DebuggerVisualizerFile>> {
+            eval_always
+            desc { "looking up the debugger visualizers of a crate" }
+        }
+
         query lint_levels(_: CrateNum) -> &'tcx lint::LintLevelMap {
             eval_always
             desc { "computing the lint levels for items in this crate" }
@@ -948,12 +956,28 @@ rustc_queries! {
     }
 
     Codegen {
+        /// Also returns, if `-Z print-stack-usage` or `-Z stack-limit-warn`
+        /// asks for the stack usage analysis, a map from the symbol name of
+        /// each function collected for codegen to the symbol names of the
+        /// functions it references.
         query collect_and_partition_mono_items(_: CrateNum)
-            -> (Arc<DefIdSet>, Arc<Vec<Arc<CodegenUnit<'tcx>>>>) {
+            -> (
+                Arc<DefIdSet>,
+                Arc<Vec<Arc<CodegenUnit<'tcx>>>>,
+                Option<Arc<FxHashMap<Symbol, Vec<Symbol>>>>,
+            ) {
             eval_always
             desc { "collect_and_partition_mono_items" }
         }
         query is_codegened_item(_: DefId) -> bool {}
+        /// Returns a bitmask in which bit `i` is set if the generic parameter
+        /// with index `i` is not used by the item, see `Instance::polymorphize`.
+        query unused_generic_params(key: DefId) -> u64 {
+            desc { |tcx|
+                "determining which generic parameters are unused by `{}`",
+                tcx.def_path_str(key)
+            }
+        }
         query codegen_unit(_: Symbol) -> Arc<CodegenUnit<'tcx>> {
             no_force
             desc { "codegen_unit" }
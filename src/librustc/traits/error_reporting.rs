@@ -835,6 +835,18 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
                                 obligation.cause.body_id,
                             );
                         } else {
+                            if let Some(param_trait_ref) =
+                                self.derived_param_trait_ref(&obligation.cause.code)
+                            {
+                                // The bound that failed is on a concrete type, but it was
+                                // introduced by an impl selected for one of the user's type
+                                // parameters: requiring that bound directly avoids the impl.
+                                self.suggest_restricting_param_bound(
+                                    &mut err,
+                                    &param_trait_ref,
+                                    obligation.cause.body_id,
+                                );
+                            }
                             if !have_alt_message {
                                 // Can't show anything else useful, try to find similar impls.
                                 let impl_candidates = self.find_similar_impl_candidates(trait_ref);
@@ -2742,16 +2754,56 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
             }
             ObligationCauseCode::ImplDerivedObligation(ref data) => {
                 let parent_trait_ref = self.resolve_vars_if_possible(&data.parent_trait_ref);
-                err.note(&format!(
+                let msg = format!(
                     "required because of the requirements on the impl of `{}` for `{}`",
                     parent_trait_ref.print_only_trait_path(),
                     parent_trait_ref.skip_binder().self_ty()
-                ));
-                let parent_predicate = parent_trait_ref.to_predicate();
+                );
+                // Point at the impl itself when it is local, since that is where the
+                // where-clause that introduced the obligation is written.
+                let impl_span =
+                    data.impl_def_id.and_then(|impl_def_id| tcx.hir().span_if_local(impl_def_id));
+                if let Some(impl_span) = impl_span {
+                    err.span_note(tcx.sess.source_map().def_span(impl_span), &msg);
+                } else {
+                    err.note(&msg);
+                }
+
+                // Deeply nested types going through the same blanket impl over and
+                // over again (e.g. `Vec<Vec<Vec<T>>>: Foo` through `impl<T: Foo> Foo
+                // for Vec<T>`) produce one identical-looking frame per level, so only
+                // keep the first and last frame of such a run.
+                let mut next = data;
+                let mut hidden = 0;
+                while let ObligationCauseCode::ImplDerivedObligation(ref child) =
+                    *next.parent_code
+                {
+                    match *child.parent_code {
+                        ObligationCauseCode::ImplDerivedObligation(ref grandchild)
+                            if data.impl_def_id.is_some()
+                                && child.impl_def_id == data.impl_def_id
+                                && grandchild.impl_def_id == data.impl_def_id =>
+                        {
+                            next = child;
+                            hidden += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                if hidden > 0 {
+                    err.note(&format!(
+                        "{} redundant requirement{} hidden",
+                        hidden,
+                        pluralize!(hidden)
+                    ));
+                }
+
+                let parent_predicate =
+                    self.resolve_vars_if_possible(&next.parent_trait_ref).to_predicate();
                 self.note_obligation_cause_code(
                     err,
                     &parent_predicate,
-                    &data.parent_code,
+                    &next.parent_code,
                     obligated_types,
                 );
             }
@@ -2793,6 +2845,29 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         }
     }
 
+    /// Walks up the chain of derived obligations in `code` and returns the
+    /// innermost parent trait reference whose self type is a type parameter.
+    fn derived_param_trait_ref(
+        &self,
+        mut code: &ObligationCauseCode<'tcx>,
+    ) -> Option<ty::PolyTraitRef<'tcx>> {
+        loop {
+            match *code {
+                ObligationCauseCode::BuiltinDerivedObligation(ref data)
+                | ObligationCauseCode::ImplDerivedObligation(ref data) => {
+                    let parent_trait_ref = self.resolve_vars_if_possible(&data.parent_trait_ref);
+                    if let ty::Param(_) = parent_trait_ref.skip_binder().self_ty().kind {
+                        if !parent_trait_ref.has_infer_types() {
+                            return Some(parent_trait_ref);
+                        }
+                    }
+                    code = &data.parent_code;
+                }
+                _ => return None,
+            }
+        }
+    }
+
     fn suggest_new_overflow_limit(&self, err: &mut DiagnosticBuilder<'_>) {
         let current_limit = self.tcx.sess.recursion_limit.get();
         let suggested_limit = current_limit * 2;
@@ -15,6 +15,7 @@ mod on_unimplemented;
 mod project;
 pub mod query;
 mod select;
+mod solver_trace;
 mod specialize;
 mod structural_impls;
 mod util;
@@ -331,6 +332,10 @@ pub struct DerivedObligationCause<'tcx> {
 
     /// The parent trait had this cause.
     parent_code: Rc<ObligationCauseCode<'tcx>>,
+
+    /// For an `ImplDerivedObligation`, the impl whose where-clauses
+    /// introduced the current obligation, if known.
+    impl_def_id: Option<DefId>,
 }
 
 pub type Obligations<'tcx, O> = Vec<Obligation<'tcx, O>>;
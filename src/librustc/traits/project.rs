@@ -472,6 +472,34 @@ fn opt_normalize_projection_type<'a, 'b, 'tcx>(
     cause: ObligationCause<'tcx>,
     depth: usize,
     obligations: &mut Vec<PredicateObligation<'tcx>>,
+) -> Option<Ty<'tcx>> {
+    selcx.trace_root(
+        "normalize",
+        &projection_ty,
+        |selcx| {
+            opt_normalize_projection_type_untraced(
+                selcx,
+                param_env,
+                projection_ty,
+                cause,
+                depth,
+                obligations,
+            )
+        },
+        |result| match result {
+            Some(ty) => format!("`{}`", ty),
+            None => "ambiguous".to_string(),
+        },
+    )
+}
+
+fn opt_normalize_projection_type_untraced<'a, 'b, 'tcx>(
+    selcx: &'a mut SelectionContext<'b, 'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    projection_ty: ty::ProjectionTy<'tcx>,
+    cause: ObligationCause<'tcx>,
+    depth: usize,
+    obligations: &mut Vec<PredicateObligation<'tcx>>,
 ) -> Option<Ty<'tcx>> {
     let infcx = selcx.infcx();
 
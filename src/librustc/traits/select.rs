@@ -10,6 +10,7 @@ use self::SelectionCandidate::*;
 use super::coherence::{self, Conflict};
 use super::project;
 use super::project::{normalize_with_depth, Normalized, ProjectionCacheKey};
+use super::solver_trace::{self, SolverTrace};
 use super::util;
 use super::DerivedObligationCause;
 use super::Selection;
@@ -88,6 +89,10 @@ pub struct SelectionContext<'cx, 'tcx> {
     /// policy. In essence, canonicalized queries need their errors propagated
     /// rather than immediately reported because we do not have accurate spans.
     query_mode: TraitQueryMode,
+
+    /// The trace of the root obligation currently being processed, if it
+    /// was requested with `-Z dump-trait-solver`.
+    trace: Option<SolverTrace>,
 }
 
 #[derive(Clone, Debug)]
@@ -476,6 +481,13 @@ pub struct EvaluationCache<'tcx> {
     >,
 }
 
+fn describe_evaluation(result: &Result<EvaluationResult, OverflowError>) -> String {
+    match *result {
+        Ok(result) => format!("{:?}", result),
+        Err(OverflowError) => "overflow".to_string(),
+    }
+}
+
 impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
     pub fn new(infcx: &'cx InferCtxt<'cx, 'tcx>) -> SelectionContext<'cx, 'tcx> {
         SelectionContext {
@@ -485,6 +497,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             intercrate_ambiguity_causes: None,
             allow_negative_impls: false,
             query_mode: TraitQueryMode::Standard,
+            trace: None,
         }
     }
 
@@ -500,6 +513,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             intercrate_ambiguity_causes: None,
             allow_negative_impls: false,
             query_mode: TraitQueryMode::Standard,
+            trace: None,
         }
     }
 
@@ -515,6 +529,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             intercrate_ambiguity_causes: None,
             allow_negative_impls,
             query_mode: TraitQueryMode::Standard,
+            trace: None,
         }
     }
 
@@ -530,6 +545,79 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             intercrate_ambiguity_causes: None,
             allow_negative_impls: false,
             query_mode,
+            trace: None,
+        }
+    }
+
+    /// Runs `op` as the root of a `-Z dump-trait-solver` trace if `predicate`
+    /// matches the filter, printing the trace once `op` returns. If a trace
+    /// is already active, `op` is recorded as a step of it instead.
+    pub(super) fn trace_root<R>(
+        &mut self,
+        kind: &str,
+        predicate: &dyn Display,
+        op: impl FnOnce(&mut Self) -> R,
+        describe: impl FnOnce(&R) -> String,
+    ) -> R {
+        if self.trace.is_some() {
+            return self.trace_step(|| format!("{} `{}`", kind, predicate), op, describe);
+        }
+
+        let sess = self.tcx().sess;
+        if sess.opts.debugging_opts.dump_trait_solver.is_none() {
+            return op(self);
+        }
+        let predicate = predicate.to_string();
+        if !solver_trace::filter_matches(sess, &predicate) {
+            return op(self);
+        }
+
+        self.trace = Some(SolverTrace::new(format!("{} `{}`", kind, predicate)));
+        let result = op(self);
+        self.trace.take().unwrap().finish(sess, describe(&result));
+        result
+    }
+
+    /// Records `op` as a nested step of the active trace, if any.
+    fn trace_step<R>(
+        &mut self,
+        label: impl FnOnce() -> String,
+        op: impl FnOnce(&mut Self) -> R,
+        describe: impl FnOnce(&R) -> String,
+    ) -> R {
+        match self.trace {
+            Some(ref mut trace) => trace.enter(label()),
+            None => return op(self),
+        }
+        let result = op(self);
+        self.trace.as_mut().unwrap().exit(describe(&result));
+        result
+    }
+
+    /// Records an event in the active trace, if any.
+    fn trace_note(&mut self, label: impl FnOnce(&Self) -> String) {
+        if self.trace.is_some() {
+            let label = label(self);
+            self.trace.as_mut().unwrap().note(label);
+        }
+    }
+
+    /// Records in the active trace, if any, that `impl_def_id` can't be used
+    /// for the obligation being selected, along with the cause.
+    fn trace_impl_rejected(&mut self, impl_def_id: DefId, cause: impl FnOnce() -> String) {
+        self.trace_note(|this| {
+            format!("impl `{}` rejected: {}", this.tcx().def_path_str(impl_def_id), cause())
+        });
+    }
+
+    fn describe_candidate(&self, candidate: &SelectionCandidate<'tcx>) -> String {
+        match *candidate {
+            ImplCandidate(def_id) => format!("impl `{}`", self.tcx().def_path_str(def_id)),
+            AutoImplCandidate(def_id) => {
+                format!("auto impl of `{}`", self.tcx().def_path_str(def_id))
+            }
+            ParamCandidate(trait_ref) => format!("where-clause `{}`", trait_ref.to_predicate()),
+            _ => format!("{:?}", candidate),
         }
     }
 
@@ -585,6 +673,22 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
     pub fn select(
         &mut self,
         obligation: &TraitObligation<'tcx>,
+    ) -> SelectionResult<'tcx, Selection<'tcx>> {
+        self.trace_root(
+            "select",
+            &obligation.predicate,
+            |this| this.select_untraced(obligation),
+            |result| match result {
+                Ok(Some(_)) => "selected".to_string(),
+                Ok(None) => "ambiguous".to_string(),
+                Err(e) => format!("error: {:?}", e),
+            },
+        )
+    }
+
+    fn select_untraced(
+        &mut self,
+        obligation: &TraitObligation<'tcx>,
     ) -> SelectionResult<'tcx, Selection<'tcx>> {
         debug!("select({:?})", obligation);
         debug_assert!(!obligation.predicate.has_escaping_bound_vars());
@@ -608,6 +712,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             Ok(Some(candidate)) => candidate,
         };
 
+        self.trace_note(|this| format!("confirming {}", this.describe_candidate(&candidate)));
         match self.confirm_candidate(obligation, candidate) {
             Err(SelectionError::Overflow) => {
                 assert!(self.query_mode == TraitQueryMode::Canonical);
@@ -648,12 +753,19 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         &mut self,
         obligation: &PredicateObligation<'tcx>,
     ) -> Result<EvaluationResult, OverflowError> {
-        self.evaluation_probe(|this| {
-            this.evaluate_predicate_recursively(
-                TraitObligationStackList::empty(&ProvisionalEvaluationCache::default()),
-                obligation.clone(),
-            )
-        })
+        self.trace_root(
+            "evaluate",
+            &obligation.predicate,
+            |this| {
+                this.evaluation_probe(|this| {
+                    this.evaluate_predicate_recursively(
+                        TraitObligationStackList::empty(&ProvisionalEvaluationCache::default()),
+                        obligation.clone(),
+                    )
+                })
+            },
+            describe_evaluation,
+        )
     }
 
     fn evaluation_probe(
@@ -699,6 +811,22 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         &mut self,
         previous_stack: TraitObligationStackList<'o, 'tcx>,
         obligation: PredicateObligation<'tcx>,
+    ) -> Result<EvaluationResult, OverflowError> {
+        if self.trace.is_none() {
+            return self.evaluate_predicate_recursively_untraced(previous_stack, obligation);
+        }
+        let label = format!("obligation `{}`", obligation.predicate);
+        self.trace_step(
+            || label,
+            |this| this.evaluate_predicate_recursively_untraced(previous_stack, obligation),
+            describe_evaluation,
+        )
+    }
+
+    fn evaluate_predicate_recursively_untraced<'o>(
+        &mut self,
+        previous_stack: TraitObligationStackList<'o, 'tcx>,
+        obligation: PredicateObligation<'tcx>,
     ) -> Result<EvaluationResult, OverflowError> {
         debug!(
             "evaluate_predicate_recursively(previous_stack={:?}, obligation={:?})",
@@ -1072,16 +1200,30 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             "evaluate_candidate: depth={} candidate={:?}",
             stack.obligation.recursion_depth, candidate
         );
-        let result = self.evaluation_probe(|this| {
-            let candidate = (*candidate).clone();
-            match this.confirm_candidate(stack.obligation, candidate) {
-                Ok(selection) => this.evaluate_predicates_recursively(
-                    stack.list(),
-                    selection.nested_obligations().into_iter(),
-                ),
-                Err(..) => Ok(EvaluatedToErr),
-            }
-        })?;
+        let label = if self.trace.is_some() {
+            format!("candidate {}", self.describe_candidate(candidate))
+        } else {
+            String::new()
+        };
+        let result = self.trace_step(
+            || label,
+            |this| {
+                this.evaluation_probe(|this| {
+                    let candidate = (*candidate).clone();
+                    match this.confirm_candidate(stack.obligation, candidate) {
+                        Ok(selection) => this.evaluate_predicates_recursively(
+                            stack.list(),
+                            selection.nested_obligations().into_iter(),
+                        ),
+                        Err(e) => {
+                            this.trace_note(|_| format!("confirmation failed: {:?}", e));
+                            Ok(EvaluatedToErr)
+                        }
+                    }
+                })
+            },
+            describe_evaluation,
+        )?;
         debug!(
             "evaluate_candidate: depth={} result={:?}",
             stack.obligation.recursion_depth, result
@@ -1360,12 +1502,18 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
 
         if candidate_set.ambiguous {
             debug!("candidate set contains ambig");
+            self.trace_note(|_| "candidate set is ambiguous".to_string());
             return Ok(None);
         }
 
         let mut candidates = candidate_set.vec;
 
         debug!("assembled {} candidates for {:?}: {:?}", candidates.len(), stack, candidates);
+        self.trace_note(|this| {
+            let candidates: Vec<_> =
+                candidates.iter().map(|c| this.describe_candidate(c)).collect();
+            format!("assembled candidates: [{}]", candidates.join(", "))
+        });
 
         // At this point, we know that each of the entries in the
         // candidate set is *individually* applicable. Now we have to
@@ -1421,6 +1569,12 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
                 });
                 if is_dup {
                     debug!("Dropping candidate #{}/{}: {:?}", i, candidates.len(), candidates[i]);
+                    self.trace_note(|this| {
+                        format!(
+                            "dropped {} in favor of another candidate",
+                            this.describe_candidate(&candidates[i].candidate)
+                        )
+                    });
                     candidates.swap_remove(i);
                 } else {
                     debug!("Retaining candidate #{}/{}: {:?}", i, candidates.len(), candidates[i]);
@@ -1430,6 +1584,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
                     // and report ambiguity.
                     if i > 1 {
                         debug!("multiple matches, ambig");
+                        self.trace_note(|_| "multiple candidates remain, ambiguous".to_string());
                         return Ok(None);
                     }
                 }
@@ -1446,6 +1601,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         // who might care about this case, like coherence, should use
         // that function).
         if candidates.is_empty() {
+            self.trace_note(|_| "no candidates apply".to_string());
             return Err(Unimplemented);
         }
 
@@ -2892,7 +3048,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         self.infcx.commit_unconditionally(|snapshot| {
             let substs = self.rematch_impl(impl_def_id, obligation, snapshot);
             debug!("confirm_impl_candidate: substs={:?}", substs);
-            let cause = obligation.derived_cause(ImplDerivedObligation);
+            let cause = obligation.impl_derived_cause(impl_def_id);
             self.vtable_impl(
                 impl_def_id,
                 substs,
@@ -3472,6 +3628,10 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         // consider a "quick reject". This avoids creating more types
         // and so forth that we need to.
         if self.fast_reject_trait_refs(obligation, &impl_trait_ref) {
+            self.trace_impl_rejected(impl_def_id, || {
+                let trait_ref = obligation.predicate.skip_binder().trait_ref;
+                format!("`{}` cannot match `{}`", impl_trait_ref, trait_ref)
+            });
             return Err(());
         }
 
@@ -3502,11 +3662,15 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             .infcx
             .at(&obligation.cause, obligation.param_env)
             .eq(skol_obligation_trait_ref, impl_trait_ref)
-            .map_err(|e| debug!("match_impl: failed eq_trait_refs due to `{}`", e))?;
+            .map_err(|e| {
+                debug!("match_impl: failed eq_trait_refs due to `{}`", e);
+                self.trace_impl_rejected(impl_def_id, || e.to_string());
+            })?;
         nested_obligations.extend(obligations);
 
         if let Err(e) = self.infcx.leak_check(false, &placeholder_map, snapshot) {
             debug!("match_impl: failed leak check due to `{}`", e);
+            self.trace_impl_rejected(impl_def_id, || e.to_string());
             return Err(());
         }
 
@@ -3514,6 +3678,9 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             && self.tcx().impl_polarity(impl_def_id) == ty::ImplPolarity::Reservation
         {
             debug!("match_impl: reservation impls only apply in intercrate mode");
+            self.trace_impl_rejected(impl_def_id, || {
+                "reservation impls only apply in intercrate mode".to_string()
+            });
             return Err(());
         }
 
@@ -3762,6 +3929,7 @@ impl<'tcx> TraitObligation<'tcx> {
             let derived_cause = DerivedObligationCause {
                 parent_trait_ref: obligation.predicate.to_poly_trait_ref(),
                 parent_code: Rc::new(obligation.cause.code.clone()),
+                impl_def_id: None,
             };
             let derived_code = variant(derived_cause);
             ObligationCause::new(obligation.cause.span, obligation.cause.body_id, derived_code)
@@ -3769,6 +3937,17 @@ impl<'tcx> TraitObligation<'tcx> {
             obligation.cause.clone()
         }
     }
+
+    /// Like `derived_cause(ImplDerivedObligation)`, but also records the impl
+    /// whose where-clauses the derived obligations come from, so that error
+    /// reporting can point at it.
+    pub fn impl_derived_cause(&self, impl_def_id: DefId) -> ObligationCause<'tcx> {
+        let mut cause = self.derived_cause(ImplDerivedObligation);
+        if let ImplDerivedObligation(ref mut data) = cause.code {
+            data.impl_def_id = Some(impl_def_id);
+        }
+        cause
+    }
 }
 
 impl<'tcx> SelectionCache<'tcx> {
@@ -0,0 +1,94 @@
+//! Support for `-Z dump-trait-solver`.
+//!
+//! When the option is set, `SelectionContext` records a tree of the steps it
+//! took while evaluating each root obligation whose predicate contains the
+//! given filter: the candidates it assembled, the impls it rejected, the
+//! nested obligations it evaluated and the projections it normalized. Once
+//! the root obligation has been processed, the tree is printed to stdout,
+//! either indented or as a single line of JSON per root obligation.
+
+use crate::session::Session;
+use rustc_serialize::json;
+
+#[derive(RustcEncodable)]
+pub struct TraceNode {
+    pub label: String,
+    pub result: Option<String>,
+    pub children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    fn new(label: String) -> TraceNode {
+        TraceNode { label, result: None, children: Vec::new() }
+    }
+
+    fn write_tree(&self, out: &mut String, depth: usize) {
+        for _ in 0..depth {
+            out.push_str("    ");
+        }
+        out.push_str(&self.label);
+        if let Some(ref result) = self.result {
+            out.push_str(" => ");
+            out.push_str(result);
+        }
+        out.push('\n');
+        for child in &self.children {
+            child.write_tree(out, depth + 1);
+        }
+    }
+}
+
+/// The trace of a single root obligation, under construction.
+pub struct SolverTrace {
+    /// The nodes that are currently being evaluated, from the root
+    /// obligation down to the innermost step.
+    stack: Vec<TraceNode>,
+}
+
+impl SolverTrace {
+    pub fn new(label: String) -> SolverTrace {
+        SolverTrace { stack: vec![TraceNode::new(label)] }
+    }
+
+    /// Starts a nested step; subsequent events are recorded as its children
+    /// until the matching call to `exit`.
+    pub fn enter(&mut self, label: String) {
+        self.stack.push(TraceNode::new(label));
+    }
+
+    /// Finishes the innermost step with the given outcome.
+    pub fn exit(&mut self, result: String) {
+        assert!(self.stack.len() > 1, "unbalanced trait solver trace");
+        let mut node = self.stack.pop().unwrap();
+        node.result = Some(result);
+        self.stack.last_mut().unwrap().children.push(node);
+    }
+
+    /// Records an event without any nested steps.
+    pub fn note(&mut self, label: String) {
+        self.stack.last_mut().unwrap().children.push(TraceNode::new(label));
+    }
+
+    /// Finishes the root obligation with the given outcome and prints the trace.
+    pub fn finish(mut self, sess: &Session, result: String) {
+        assert_eq!(self.stack.len(), 1, "unbalanced trait solver trace");
+        let mut root = self.stack.pop().unwrap();
+        root.result = Some(result);
+        if sess.opts.debugging_opts.dump_trait_solver_json {
+            println!("{}", json::as_json(&root));
+        } else {
+            let mut out = String::new();
+            root.write_tree(&mut out, 0);
+            print!("{}", out);
+        }
+    }
+}
+
+/// Returns `true` if `-Z dump-trait-solver` asks for a trace of obligations
+/// whose predicate is printed as `predicate`.
+pub fn filter_matches(sess: &Session, predicate: &str) -> bool {
+    match sess.opts.debugging_opts.dump_trait_solver {
+        Some(ref filter) => filter == "all" || predicate.contains(filter.as_str()),
+        None => false,
+    }
+}
@@ -546,6 +546,7 @@ impl<'a, 'tcx> Lift<'tcx> for traits::DerivedObligationCause<'a> {
             tcx.lift(&*self.parent_code).map(|code| traits::DerivedObligationCause {
                 parent_trait_ref: trait_ref,
                 parent_code: Rc::new(code),
+                impl_def_id: self.impl_def_id,
             })
         })
     }
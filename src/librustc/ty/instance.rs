@@ -4,6 +4,7 @@ use crate::hir::CodegenFnAttrFlags;
 use crate::middle::lang_items::DropInPlaceFnLangItem;
 use crate::traits;
 use crate::ty::print::{FmtPrinter, Printer};
+use crate::ty::subst::InternalSubsts;
 use crate::ty::{self, SubstsRef, Ty, TyCtxt, TypeFoldable};
 use rustc_macros::HashStable;
 use rustc_target::spec::abi::Abi;
@@ -319,6 +320,36 @@ impl<'tcx> Instance<'tcx> {
     pub fn is_vtable_shim(&self) -> bool {
         if let InstanceDef::VtableShim(..) = self.def { true } else { false }
     }
+
+    /// Replaces the substitutions for the generic parameters that the item
+    /// never uses with the parameters themselves, so that instantiations which
+    /// only differ in those parameters share a single copy of the item. This
+    /// is a no-op unless `-Z polymorphize` is enabled.
+    pub fn polymorphize(self, tcx: TyCtxt<'tcx>) -> Instance<'tcx> {
+        let def_id = match self.def {
+            InstanceDef::Item(def_id) => def_id,
+            _ => return self,
+        };
+        if !tcx.sess.opts.debugging_opts.polymorphize || self.substs.is_empty() {
+            return self;
+        }
+
+        let unused = tcx.unused_generic_params(def_id);
+        if unused == 0 {
+            return self;
+        }
+
+        let substs = InternalSubsts::for_item(tcx, def_id, |param, _| {
+            let index = param.index as usize;
+            if index < 64 && unused & (1 << index) != 0 {
+                tcx.mk_param_from_def(param)
+            } else {
+                self.substs[index]
+            }
+        });
+        debug!("polymorphize({:?}) = {:?}", self, substs);
+        Instance { def: self.def, substs }
+    }
 }
 
 fn resolve_associated_item<'tcx>(
@@ -1,4 +1,4 @@
-use crate::session::{self, DataTypeKind};
+use crate::session::{self, DataTypeKind, FieldKind};
 use crate::ty::{self, subst::SubstsRef, ReprOptions, Ty, TyCtxt, TypeFoldable};
 
 use syntax::ast::{self, Ident, IntTy, UintTy};
@@ -1280,6 +1280,7 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
     fn generator_saved_local_eligibility(
         &self,
         info: &GeneratorLayout<'tcx>,
+        saved_local_sizes: &IndexVec<GeneratorSavedLocal, Size>,
     ) -> (BitSet<GeneratorSavedLocal>, IndexVec<GeneratorSavedLocal, SavedLocalEligibility>) {
         use SavedLocalEligibility::*;
 
@@ -1324,6 +1325,12 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
             }
 
             for local_b in info.storage_conflicts.iter(local_a) {
+                // Once `local_a` is promoted its remaining conflicts are
+                // resolved, don't promote any more locals on its account.
+                if ineligible_locals.contains(local_a) {
+                    break;
+                }
+
                 // local_a and local_b are storage live at the same time, therefore they
                 // cannot overlap in the generator layout. The only way to guarantee
                 // this is if they are in the same variant, or one is ineligible
@@ -1337,9 +1344,16 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
                 // If they conflict, we will choose one to make ineligible.
                 // This is not always optimal; it's just a greedy heuristic that
                 // seems to produce good results most of the time.
+                //
+                // Promoting a local to the prefix costs its size in every
+                // variant, but resolves all of its conflicts at once. So we
+                // promote the local with the smallest size per conflict, which
+                // for locals of equal size means the one with more conflicts.
                 let conflicts_b = info.storage_conflicts.count(local_b);
+                let cost_a = saved_local_sizes[local_a].bytes() as u128 * conflicts_b as u128;
+                let cost_b = saved_local_sizes[local_b].bytes() as u128 * conflicts_a as u128;
                 let (remove, other) =
-                    if conflicts_a > conflicts_b { (local_a, local_b) } else { (local_b, local_a) };
+                    if cost_a < cost_b { (local_a, local_b) } else { (local_b, local_a) };
                 ineligible_locals.insert(remove);
                 assignments[remove] = Ineligible(None);
                 trace!("removing local {:?} due to conflict with {:?}", remove, other);
@@ -1395,7 +1409,13 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
         let subst_field = |ty: Ty<'tcx>| ty.subst(tcx, substs);
 
         let info = tcx.generator_layout(def_id);
-        let (ineligible_locals, assignments) = self.generator_saved_local_eligibility(&info);
+        let saved_local_sizes = info
+            .field_tys
+            .iter()
+            .map(|&ty| self.layout_of(subst_field(ty)).map(|layout| layout.size))
+            .collect::<Result<IndexVec<GeneratorSavedLocal, _>, _>>()?;
+        let (ineligible_locals, assignments) =
+            self.generator_saved_local_eligibility(&info, &saved_local_sizes);
 
         // Build a prefix layout, including "promoting" all ineligible
         // locals as part of the prefix. We compute the layout of all of
@@ -1616,6 +1636,28 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
                 return;
             }
 
+            ty::Generator(def_id, substs, movability) => {
+                debug!("print-type-size t: `{:?}` record generator", layout.ty);
+                // The full type lists every type saved across suspension
+                // points, so only describe the generator by its location.
+                let kind = match movability {
+                    hir::Movability::Movable => "generator",
+                    hir::Movability::Static => "static generator",
+                };
+                let (variant_infos, discr_size) =
+                    self.generator_variant_infos(layout, def_id, substs);
+                self.tcx.sess.code_stats.record_type_size(
+                    DataTypeKind::Generator,
+                    format!("[{}@{:?}]", kind, self.tcx.def_span(def_id)),
+                    layout.align.abi,
+                    layout.size,
+                    false,
+                    discr_size,
+                    variant_infos,
+                );
+                return;
+            }
+
             _ => {
                 debug!("print-type-size t: `{:?}` skip non-nominal", layout.ty);
                 return;
@@ -1641,6 +1683,7 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
                             min_size = field_end;
                         }
                         session::FieldInfo {
+                            kind: FieldKind::AdtField,
                             name: name.to_string(),
                             offset: offset.bytes(),
                             size: field_layout.size.bytes(),
@@ -1713,6 +1756,79 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
             }
         }
     }
+
+    /// Describes every state of a generator for `-Z print-type-sizes`. The
+    /// upvars are stored in the prefix shared by all states, so they are
+    /// listed in each of them, like the saved locals promoted to the prefix.
+    fn generator_variant_infos(
+        &self,
+        layout: TyLayout<'tcx>,
+        def_id: hir::def_id::DefId,
+        substs: SubstsRef<'tcx>,
+    ) -> (Vec<session::VariantInfo>, Option<Size>) {
+        let (discr, discr_index) = match layout.variants {
+            Variants::Multiple { ref discr, discr_index, .. } => (discr, discr_index),
+            Variants::Single { .. } => return (vec![], None),
+        };
+        let tcx = self.tcx;
+        let info = tcx.generator_layout(def_id);
+        let discr_size = discr.value.size(self);
+        let discr_end = layout.fields.offset(discr_index) + discr_size;
+
+        let field_info = |kind, name: String, layout: TyLayout<'tcx>, i: usize| {
+            let field_layout = match layout.field(self, i) {
+                Ok(field_layout) => field_layout,
+                Err(err) => bug!("no layout found for field {}: `{:?}`", name, err),
+            };
+            session::FieldInfo {
+                kind,
+                name,
+                offset: layout.fields.offset(i).bytes(),
+                size: field_layout.size.bytes(),
+                align: field_layout.align.abi.bytes(),
+            }
+        };
+
+        let upvars: Vec<_> = tcx
+            .upvars(def_id)
+            .iter()
+            .flat_map(|upvars| upvars.keys())
+            .enumerate()
+            .map(|(i, &var_id)| {
+                field_info(FieldKind::Upvar, tcx.hir().name(var_id).to_string(), layout, i)
+            })
+            .collect();
+
+        let variant_infos = info
+            .variant_fields
+            .iter_enumerated()
+            .map(|(index, variant_fields)| {
+                let variant_layout = layout.for_variant(self, index);
+                let mut fields = upvars.clone();
+                fields.extend(variant_fields.iter().enumerate().map(|(i, &local)| {
+                    let name = match info.field_names[local] {
+                        Some(name) => name.to_string(),
+                        None => format!("__{}", local.as_usize()),
+                    };
+                    field_info(FieldKind::GeneratorLocal, name, variant_layout, i)
+                }));
+                // The discriminant is part of every state, even of those that
+                // store nothing after it.
+                let size = fields
+                    .iter()
+                    .map(|field| field.offset + field.size)
+                    .fold(discr_end.bytes(), cmp::max);
+                session::VariantInfo {
+                    name: Some(substs.as_generator().variant_name(index).into_owned()),
+                    kind: session::SizeKind::Exact,
+                    align: variant_layout.align.abi.bytes(),
+                    size,
+                    fields,
+                }
+            })
+            .collect();
+        (variant_infos, Some(discr_size))
+    }
 }
 
 /// Type size "skeleton", i.e., the only information determining a type's size.
@@ -91,7 +91,7 @@ pub use self::structural_match::search_for_structural_match_violation;
 pub use self::structural_match::type_marked_structural;
 pub use self::structural_match::NonStructuralMatchTy;
 
-pub use self::trait_def::TraitDef;
+pub use self::trait_def::{TraitDef, TraitSpecializationKind};
 
 pub use self::query::queries;
 
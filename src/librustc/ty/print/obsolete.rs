@@ -143,13 +143,14 @@ impl DefPathBasedNames<'tcx> {
                 let substs = substs.truncate_to(self.tcx, generics);
                 self.push_generic_params(substs, iter::empty(), output, debug);
             }
+            // Polymorphized instances keep the parameters they do not use.
+            ty::Param(param) => output.push_str(&param.name.as_str()),
             ty::Error
             | ty::Bound(..)
             | ty::Infer(_)
             | ty::Placeholder(..)
             | ty::UnnormalizedProjection(..)
             | ty::Projection(..)
-            | ty::Param(_)
             | ty::GeneratorWitness(_)
             | ty::Opaque(..) => {
                 if debug {
@@ -171,6 +172,8 @@ impl DefPathBasedNames<'tcx> {
         if let ty::ConstKind::Value(_) = c.val {
             // FIXME(const_generics): we could probably do a better job here.
             write!(output, "{:?}", c).unwrap()
+        } else if let ty::ConstKind::Param(param) = c.val {
+            write!(output, "{}", param.name).unwrap()
         } else if debug {
             write!(output, "{:?}", c).unwrap()
         } else {
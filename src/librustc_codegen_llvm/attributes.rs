@@ -97,6 +97,31 @@ fn set_instrument_function(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
     }
 }
 
+/// Applies the attribute requesting instrumentation by the active sanitizer,
+/// unless the function opted out of it with `#[no_sanitize]`.
+pub fn sanitize(cx: &CodegenCx<'ll, '_>, codegen_fn_flags: CodegenFnAttrFlags, llfn: &'ll Value) {
+    let (attribute, no_sanitize) = match cx.sess().opts.debugging_opts.sanitizer {
+        Some(Sanitizer::Address) | Some(Sanitizer::KernelAddress) => {
+            (Attribute::SanitizeAddress, CodegenFnAttrFlags::NO_SANITIZE_ADDRESS)
+        }
+        Some(Sanitizer::HwAddress) => {
+            (Attribute::SanitizeHWAddress, CodegenFnAttrFlags::NO_SANITIZE_HWADDRESS)
+        }
+        Some(Sanitizer::Memory) => {
+            (Attribute::SanitizeMemory, CodegenFnAttrFlags::NO_SANITIZE_MEMORY)
+        }
+        Some(Sanitizer::Thread) => {
+            (Attribute::SanitizeThread, CodegenFnAttrFlags::NO_SANITIZE_THREAD)
+        }
+        _ => return,
+    };
+    if codegen_fn_flags.contains(no_sanitize) {
+        attribute.unapply_llfn(Function, llfn);
+    } else {
+        attribute.apply_llfn(Function, llfn);
+    }
+}
+
 fn set_probestack(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
     // Only use stack probes if the target specification indicates that we
     // should be using stack probes
@@ -108,7 +133,9 @@ fn set_probestack(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
     // sanitizer and thread sanitizer. With asan we're already protected from
     // stack overflow anyway so we don't really need stack probes regardless.
     match cx.sess().opts.debugging_opts.sanitizer {
-        Some(Sanitizer::Address) | Some(Sanitizer::Thread) => return,
+        Some(Sanitizer::Address) | Some(Sanitizer::KernelAddress) | Some(Sanitizer::Thread) => {
+            return;
+        }
         _ => {}
     }
 
@@ -266,6 +293,7 @@ pub fn from_fn_attrs(
     set_frame_pointer_elimination(cx, llfn);
     set_instrument_function(cx, llfn);
     set_probestack(cx, llfn);
+    sanitize(cx, codegen_fn_attrs.flags, llfn);
 
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
         Attribute::Cold.apply_llfn(Function, llfn);
@@ -0,0 +1,160 @@
+//! Reading the `.stack_sizes` section that LLVM emits with `-Z emit-stack-sizes`.
+//!
+//! The section consists of one entry per function: the address of the function
+//! (a pointer-sized field that is relocated against the function's symbol),
+//! followed by the size of its stack frame as an ULEB128 number.
+
+use crate::llvm::{self, False, ObjectFile};
+
+use rustc::session::Session;
+use rustc::util::nodemap::FxHashMap;
+use rustc_fs_util::path_to_c_string;
+
+use std::path::Path;
+use std::slice;
+
+const STACK_SIZES_SECTION_NAME: &[u8] = b".stack_sizes";
+
+/// What the relocation of the address of a function refers to.
+enum RelocationTarget {
+    /// The symbol of the function.
+    Function(String),
+    /// A symbol that isn't a function, which for functions with internal
+    /// linkage usually is the symbol of the section containing the function.
+    /// The function is then found by its section index and its offset in the
+    /// section, which is the addend of the relocation.
+    SectionOffset(u64, i64),
+}
+
+/// Returns the symbol name and stack frame size of every function in the
+/// object file at `path`, or `None` if the file could not be read.
+pub fn read_stack_sizes(sess: &Session, path: &Path) -> Option<Vec<(String, u64)>> {
+    let pointer_bytes = sess.target.target.target_pointer_width.parse::<usize>().unwrap() / 8;
+    let big_endian = sess.target.target.target_endian == "big";
+    let mut stack_sizes = Vec::new();
+
+    unsafe {
+        let buf = path_to_c_string(path);
+        let mb = llvm::LLVMRustCreateMemoryBufferWithContentsOfFile(buf.as_ptr())?;
+        let of = ObjectFile::new(mb)?;
+
+        // The functions defined in the object file, keyed by the index of
+        // their section and their offset in it.
+        let mut functions = FxHashMap::default();
+        let symi = llvm::mk_symbol_iter(of.llof);
+        let si = llvm::mk_section_iter(of.llof);
+        while llvm::LLVMIsSymbolIteratorAtEnd(of.llof, symi.llsi) == False {
+            if llvm::LLVMRustIsFunctionSymbol(symi.llsi) {
+                llvm::LLVMMoveToContainingSection(si.llsi, symi.llsi);
+                if llvm::LLVMIsSectionIteratorAtEnd(of.llof, si.llsi) == False {
+                    let section = llvm::LLVMRustGetSectionIndex(si.llsi);
+                    let offset = llvm::LLVMGetSymbolAddress(symi.llsi);
+                    functions.insert((section, offset), llvm::get_symbol_name(symi.llsi));
+                }
+            }
+            llvm::LLVMMoveToNextSymbol(symi.llsi);
+        }
+
+        // The contents of every `.stack_sizes` section keyed by its index, and
+        // the relocations applying to any section, along with the index of
+        // that section. The relocation sections refer to the section they
+        // apply to by index, which is how they are paired with the
+        // `.stack_sizes` sections.
+        let mut sections = FxHashMap::default();
+        let mut relocations = Vec::new();
+
+        let si = llvm::mk_section_iter(of.llof);
+        while llvm::LLVMIsSectionIteratorAtEnd(of.llof, si.llsi) == False {
+            let mut name_buf = None;
+            let name_len = llvm::LLVMRustGetSectionName(si.llsi, &mut name_buf);
+            let name = name_buf.map_or(&[][..], |buf| {
+                slice::from_raw_parts(buf.as_ptr() as *const u8, name_len as usize)
+            });
+            if name == STACK_SIZES_SECTION_NAME {
+                let contents = slice::from_raw_parts(
+                    llvm::LLVMGetSectionContents(si.llsi) as *const u8,
+                    llvm::LLVMGetSectionSize(si.llsi) as usize,
+                );
+                sections.insert(llvm::LLVMRustGetSectionIndex(si.llsi), contents);
+            }
+
+            let relocated_section = llvm::LLVMRustGetRelocatedSectionIndex(si.llsi);
+            if relocated_section != 0 {
+                let ri = llvm::mk_relocation_iter(si.llsi);
+                while llvm::LLVMIsRelocationIteratorAtEnd(si.llsi, ri.llri) == False {
+                    let offset = llvm::LLVMGetRelocationOffset(ri.llri) as usize;
+                    let symi = llvm::mk_relocation_symbol_iter(ri.llri);
+                    let target = if llvm::LLVMRustIsFunctionSymbol(symi.llsi) {
+                        RelocationTarget::Function(llvm::get_symbol_name(symi.llsi))
+                    } else {
+                        let symbol_si = llvm::mk_section_iter(of.llof);
+                        llvm::LLVMMoveToContainingSection(symbol_si.llsi, symi.llsi);
+                        let section = llvm::LLVMRustGetSectionIndex(symbol_si.llsi);
+                        let addend = llvm::LLVMRustGetRelocationAddend(ri.llri);
+                        RelocationTarget::SectionOffset(section, addend)
+                    };
+                    relocations.push((relocated_section, offset, target));
+                    llvm::LLVMMoveToNextRelocation(ri.llri);
+                }
+            }
+            llvm::LLVMMoveToNextSection(si.llsi);
+        }
+
+        // Every entry starts with a relocation against the function it
+        // describes, so walking the relocations visits every entry.
+        for (section, offset, target) in relocations {
+            let data = match sections.get(&section) {
+                Some(data) => data,
+                None => continue,
+            };
+            let symbol = match target {
+                RelocationTarget::Function(symbol) => symbol,
+                RelocationTarget::SectionOffset(section, addend) => {
+                    // Targets using relocations without an explicit addend
+                    // store it in the relocated field instead.
+                    let implicit_addend = match read_uint(data, offset, pointer_bytes, big_endian) {
+                        Some(implicit_addend) => implicit_addend,
+                        None => continue,
+                    };
+                    let function_offset = (addend as u64).wrapping_add(implicit_addend);
+                    match functions.get(&(section, function_offset)) {
+                        Some(symbol) => symbol.clone(),
+                        None => continue,
+                    }
+                }
+            };
+            if let Some(size) = read_uleb128(data, offset + pointer_bytes) {
+                stack_sizes.push((symbol, size));
+            }
+        }
+    }
+
+    Some(stack_sizes)
+}
+
+fn read_uint(data: &[u8], position: usize, size: usize, big_endian: bool) -> Option<u64> {
+    let bytes = data.get(position..position + size)?;
+    let push_byte = |result: u64, &byte: &u8| result << 8 | u64::from(byte);
+    if big_endian {
+        Some(bytes.iter().fold(0, push_byte))
+    } else {
+        Some(bytes.iter().rev().fold(0, push_byte))
+    }
+}
+
+fn read_uleb128(data: &[u8], mut position: usize) -> Option<u64> {
+    let mut result = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(position)?;
+        position += 1;
+        result |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return None;
+        }
+    }
+}
@@ -29,6 +29,7 @@ use std::ffi::CString;
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::ptr;
 use std::slice;
 use std::str;
 use std::sync::Arc;
@@ -70,11 +71,21 @@ pub fn write_output_file(
     pm: &llvm::PassManager<'ll>,
     m: &'ll llvm::Module,
     output: &Path,
+    dwo_output: Option<&Path>,
     file_type: llvm::FileType,
 ) -> Result<(), FatalError> {
     unsafe {
         let output_c = path_to_c_string(output);
-        let result = llvm::LLVMRustWriteOutputFile(target, pm, m, output_c.as_ptr(), file_type);
+        let dwo_output_c = dwo_output.map(path_to_c_string);
+        let dwo_output_ptr = dwo_output_c.as_ref().map_or(ptr::null(), |s| s.as_ptr());
+        let result = llvm::LLVMRustWriteOutputFile(
+            target,
+            pm,
+            m,
+            output_c.as_ptr(),
+            dwo_output_ptr,
+            file_type,
+        );
         result.into_result().map_err(|()| {
             let msg = format!("could not write output to {}", output.display());
             llvm_err(handler, &msg)
@@ -163,7 +174,10 @@ pub fn target_machine_factory(
     let abi = SmallCStr::new(&sess.target.target.options.llvm_abiname);
     let is_pie_binary = !find_features && is_pie_binary(sess);
     let trap_unreachable = sess.target.target.options.trap_unreachable;
-    let emit_stack_size_section = sess.opts.debugging_opts.emit_stack_sizes;
+    // The stack usage analysis reads the stack sizes back from the objects.
+    let emit_stack_size_section = sess.opts.debugging_opts.emit_stack_sizes
+        || sess.opts.debugging_opts.print_stack_usage
+        || sess.opts.debugging_opts.stack_limit_warn.is_some();
 
     let asm_comments = sess.asm_comments();
     let relax_elf_relocations = sess.target.target.options.relax_elf_relocations;
@@ -457,8 +471,17 @@ unsafe fn add_sanitizer_passes(config: &ModuleConfig, passes: &mut Vec<&'static
     let recover = config.sanitizer_recover.contains(sanitizer);
     match sanitizer {
         Sanitizer::Address => {
-            passes.push(llvm::LLVMRustCreateAddressSanitizerFunctionPass(recover));
-            passes.push(llvm::LLVMRustCreateModuleAddressSanitizerPass(recover));
+            passes.push(llvm::LLVMRustCreateAddressSanitizerFunctionPass(false, recover));
+            passes.push(llvm::LLVMRustCreateModuleAddressSanitizerPass(false, recover));
+        }
+        Sanitizer::KernelAddress => {
+            // The kernel reports errors and carries on, so errors are always
+            // recoverable.
+            passes.push(llvm::LLVMRustCreateAddressSanitizerFunctionPass(true, true));
+            passes.push(llvm::LLVMRustCreateModuleAddressSanitizerPass(true, true));
+        }
+        Sanitizer::HwAddress => {
+            passes.push(llvm::LLVMRustCreateHWAddressSanitizerPass(recover));
         }
         Sanitizer::Memory => {
             let track_origins = config.sanitizer_memory_track_origins as c_int;
@@ -467,7 +490,8 @@ unsafe fn add_sanitizer_passes(config: &ModuleConfig, passes: &mut Vec<&'static
         Sanitizer::Thread => {
             passes.push(llvm::LLVMRustCreateThreadSanitizerPass());
         }
-        Sanitizer::Leak => {}
+        // The control flow integrity checks are lowered by the LTO passes.
+        Sanitizer::Leak | Sanitizer::Cfi => {}
     }
 }
 
@@ -627,6 +651,7 @@ pub(crate) unsafe fn codegen(
                             cpm,
                             llmod,
                             &path,
+                            None,
                             llvm::FileType::AssemblyFile,
                         )
                     })?;
@@ -634,6 +659,11 @@ pub(crate) unsafe fn codegen(
 
                 if write_obj {
                     let _timer = cgcx.prof.generic_activity("LLVM_module_codegen_emit_obj");
+                    let dwo_out = if config.emit_dwarf_obj {
+                        Some(cgcx.output_filenames.temp_path_dwo(module_name))
+                    } else {
+                        None
+                    };
                     with_codegen(tm, llmod, config.no_builtins, |cpm| {
                         write_output_file(
                             diag_handler,
@@ -641,6 +671,7 @@ pub(crate) unsafe fn codegen(
                             cpm,
                             llmod,
                             &obj_out,
+                            dwo_out.as_deref(),
                             llvm::FileType::ObjectFile,
                         )
                     })?;
@@ -679,6 +710,7 @@ pub(crate) unsafe fn codegen(
         config.emit_obj,
         config.emit_bc,
         config.emit_bc_compressed,
+        config.emit_dwarf_obj,
         &cgcx.output_filenames,
     ))
 }
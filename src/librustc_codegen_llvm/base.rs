@@ -147,6 +147,10 @@ pub fn compile_codegen_unit(
                 }
             }
 
+            // Finalize code coverage, which adds the coverage map to the
+            // llvm.used variable
+            cx.coverageinfo_finalize();
+
             // Create the llvm.used variable
             // This variable has type [N x i8*] and is stored in the llvm.metadata section
             if !cx.used_statics().borrow().is_empty() {
@@ -29,7 +29,11 @@ pub fn get_fn(cx: &CodegenCx<'ll, 'tcx>, instance: Instance<'tcx>) -> &'ll Value
 
     assert!(!instance.substs.needs_infer());
     assert!(!instance.substs.has_escaping_bound_vars());
-    assert!(!instance.substs.has_param_types());
+    assert!(!instance.substs.has_param_types() || tcx.sess.opts.debugging_opts.polymorphize);
+
+    // Refer to the single copy shared by all instantiations that only differ
+    // in generic parameters the function does not use, see `Instance::polymorphize`.
+    let instance = instance.polymorphize(tcx);
 
     if let Some(&llfn) = cx.instances.borrow().get(&instance) {
         return llfn;
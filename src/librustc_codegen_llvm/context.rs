@@ -1,5 +1,6 @@
 use crate::abi::FnAbi;
 use crate::attributes;
+use crate::coverageinfo;
 use crate::debuginfo;
 use crate::llvm;
 use crate::llvm_util;
@@ -84,6 +85,7 @@ pub struct CodegenCx<'ll, 'tcx> {
     pub pointee_infos: RefCell<FxHashMap<(Ty<'tcx>, Size), Option<PointeeInfo>>>,
     pub isize_ty: &'ll Type,
 
+    pub coverage_cx: Option<coverageinfo::CrateCoverageContext<'ll, 'tcx>>,
     pub dbg_cx: Option<debuginfo::CrateDebugContext<'ll, 'tcx>>,
 
     eh_personality: Cell<Option<&'ll Value>>,
@@ -286,6 +288,12 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
             None
         };
 
+        let coverage_cx = if tcx.sess.opts.debugging_opts.instrument_coverage {
+            Some(coverageinfo::CrateCoverageContext::new())
+        } else {
+            None
+        };
+
         let isize_ty = Type::ix_llcx(llcx, tcx.data_layout.pointer_size.bits());
 
         CodegenCx {
@@ -307,6 +315,7 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
             scalar_lltypes: Default::default(),
             pointee_infos: Default::default(),
             isize_ty,
+            coverage_cx,
             dbg_cx,
             eh_personality: Cell::new(None),
             eh_unwind_resume: Cell::new(None),
@@ -463,6 +472,10 @@ impl MiscMethods<'tcx> for CodegenCx<'ll, 'tcx> {
             llvm::LLVMSetSection(g, section.as_ptr());
         }
     }
+
+    fn add_type_metadata(&self, global: &'ll Value, offset: u64, typeid: &str) {
+        llvm::add_type_metadata(global, offset, typeid)
+    }
 }
 
 impl CodegenCx<'b, 'tcx> {
@@ -551,6 +564,7 @@ impl CodegenCx<'b, 'tcx> {
         ifn!("llvm.debugtrap", fn() -> void);
         ifn!("llvm.frameaddress", fn(t_i32) -> i8p);
         ifn!("llvm.sideeffect", fn() -> void);
+        ifn!("llvm.instrprof.increment", fn(i8p, t_i64, t_i32, t_i32) -> void);
 
         ifn!("llvm.powi.f32", fn(t_f32, t_i32) -> t_f32);
         ifn!("llvm.powi.v2f32", fn(t_v2f32, t_i32) -> t_v2f32);
@@ -821,6 +835,7 @@ impl CodegenCx<'b, 'tcx> {
         ifn!("llvm.x86.seh.recoverfp", fn(i8p, i8p) -> i8p);
 
         ifn!("llvm.assume", fn(i1) -> void);
+        ifn!("llvm.type.test", fn(i8p, self.type_metadata()) -> i1);
         ifn!("llvm.prefetch", fn(i8p, t_i32, t_i32, t_i32) -> void);
 
         // variadic intrinsics
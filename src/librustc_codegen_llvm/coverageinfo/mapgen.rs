@@ -0,0 +1,180 @@
+//! Writing the coverage map of a codegen unit.
+//!
+//! The coverage map is the `__llvm_coverage_mapping` global, placed in the
+//! section that `llvm-cov` reads from the instrumented binary. It consists of
+//! a header, a record for every function that has counters, the names of the
+//! source files of the codegen unit, and the regions of code covered by the
+//! counters of every function, in the encoding of LLVM's `CoverageMapping`
+//! library. See <https://llvm.org/docs/CoverageMappingFormat.html>.
+
+use super::FunctionCoverage;
+use crate::common::CodegenCx;
+use crate::llvm;
+
+use libc::c_uint;
+use rustc::util::nodemap::FxHashMap;
+use rustc_codegen_ssa::traits::*;
+use syntax_pos::FileName;
+
+use std::ffi::CString;
+
+pub fn finalize(cx: &CodegenCx<'ll, 'tcx>) {
+    let function_coverage_map = cx.coverage_cx.as_ref().unwrap().take_function_coverage_map();
+    if function_coverage_map.is_empty() {
+        return;
+    }
+
+    // Sort the functions by name, so that the coverage map doesn't depend on
+    // the order of the hash map.
+    let mut functions: Vec<_> = function_coverage_map
+        .into_iter()
+        .map(|(instance, coverage)| (cx.tcx.symbol_name(instance).name.to_string(), coverage))
+        .collect();
+    functions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut filenames = Filenames::default();
+    let mut function_records = Vec::with_capacity(functions.len());
+    let mut coverage_mappings = Vec::new();
+    for (name, coverage) in functions {
+        let mapping = write_function_mapping(cx, &coverage, &mut filenames);
+        let name = CString::new(name).unwrap();
+        let name_ref = unsafe { llvm::LLVMRustCoverageComputeHash(name.as_ptr()) };
+        function_records.push(cx.const_struct(
+            &[
+                cx.const_u64(name_ref),
+                cx.const_u32(mapping.len() as u32),
+                cx.const_u64(coverage.source_hash),
+            ],
+            true,
+        ));
+        coverage_mappings.extend(mapping);
+    }
+
+    let filenames_section = llvm::build_byte_buffer(|buffer| unsafe {
+        let filenames: Vec<_> = filenames.names.iter().map(|name| name.as_ptr()).collect();
+        llvm::LLVMRustCoverageWriteFilenamesSectionToBuffer(
+            filenames.as_ptr(),
+            filenames.len(),
+            buffer,
+        );
+    });
+
+    let header = cx.const_struct(
+        &[
+            cx.const_u32(function_records.len() as u32),
+            cx.const_u32(filenames_section.len() as u32),
+            cx.const_u32(coverage_mappings.len() as u32),
+            cx.const_u32(unsafe { llvm::LLVMRustCoverageMappingVersion() }),
+        ],
+        false,
+    );
+
+    // The filenames and the mappings are padded so that the next coverage map
+    // of the section, from another object file, is aligned.
+    let mut data = filenames_section;
+    data.extend(coverage_mappings);
+    while data.len() % 8 != 0 {
+        data.push(0);
+    }
+
+    let function_records = cx.const_array(cx.val_ty(function_records[0]), &function_records);
+    let coverage_map = cx.const_struct(&[header, function_records, cx.const_bytes(&data)], false);
+
+    let var_name = llvm::build_string(|s| unsafe {
+        llvm::LLVMRustCoverageWriteMappingVarNameToString(s);
+    })
+    .expect("coverage mapping variable name is not UTF-8");
+    let section_name = llvm::build_string(|s| unsafe {
+        llvm::LLVMRustCoverageWriteSectionNameToString(cx.llmod, s);
+    })
+    .expect("coverage mapping section name is not UTF-8");
+    let section_name = CString::new(section_name).unwrap();
+
+    let coverage_map_var = cx.define_global(&var_name, cx.val_ty(coverage_map)).unwrap();
+    unsafe {
+        llvm::LLVMSetInitializer(coverage_map_var, coverage_map);
+        llvm::LLVMSetGlobalConstant(coverage_map_var, llvm::True);
+        llvm::LLVMRustSetLinkage(coverage_map_var, llvm::Linkage::PrivateLinkage);
+        llvm::LLVMSetSection(coverage_map_var, section_name.as_ptr());
+        llvm::LLVMSetAlignment(coverage_map_var, 8);
+        let cast = llvm::LLVMConstPointerCast(coverage_map_var, cx.type_i8p());
+        cx.used_statics.borrow_mut().push(cast);
+    }
+}
+
+/// The source files referenced by the regions of a codegen unit.
+#[derive(Default)]
+struct Filenames {
+    names: Vec<CString>,
+    ids: FxHashMap<FileName, u32>,
+}
+
+impl Filenames {
+    fn id(&mut self, cx: &CodegenCx<'_, '_>, file_name: &FileName) -> u32 {
+        if let Some(&id) = self.ids.get(file_name) {
+            return id;
+        }
+
+        // `llvm-cov` looks up the source files relative to its own working
+        // directory, which is not necessarily that of the compiler.
+        let name = match file_name {
+            FileName::Real(path) => cx.sess().working_dir.0.join(path).display().to_string(),
+            _ => file_name.to_string(),
+        };
+        let id = self.names.len() as u32;
+        self.names.push(CString::new(name).unwrap());
+        self.ids.insert(file_name.clone(), id);
+        id
+    }
+}
+
+/// Encodes the regions of the counters of a function.
+fn write_function_mapping(
+    cx: &CodegenCx<'_, '_>,
+    coverage: &FunctionCoverage,
+    filenames: &mut Filenames,
+) -> Vec<u8> {
+    let source_map = cx.sess().source_map();
+    // The regions of a function refer to its files by their index in the
+    // virtual file mapping, which holds their index in the filenames.
+    let mut virtual_file_mapping: Vec<c_uint> = Vec::new();
+    let mut regions = Vec::new();
+    for (counter_id, region) in coverage.regions.iter().enumerate() {
+        let region = match region {
+            Some(region) => region,
+            None => continue,
+        };
+        let start = source_map.lookup_char_pos(region.lo());
+        let end = source_map.lookup_char_pos(region.hi());
+        if start.file.name != end.file.name {
+            continue;
+        }
+
+        let filename_id = filenames.id(cx, &start.file.name);
+        let file_id = match virtual_file_mapping.iter().position(|&id| id == filename_id) {
+            Some(file_id) => file_id,
+            None => {
+                virtual_file_mapping.push(filename_id);
+                virtual_file_mapping.len() - 1
+            }
+        };
+        regions.push(llvm::CounterMappingRegion {
+            counter_id: counter_id as u32,
+            file_id: file_id as u32,
+            line_start: start.line as u32,
+            column_start: start.col.to_usize() as u32 + 1,
+            line_end: end.line as u32,
+            column_end: end.col.to_usize() as u32 + 1,
+        });
+    }
+
+    llvm::build_byte_buffer(|buffer| unsafe {
+        llvm::LLVMRustCoverageWriteMappingToBuffer(
+            virtual_file_mapping.as_ptr(),
+            virtual_file_mapping.len() as c_uint,
+            regions.as_ptr(),
+            regions.len() as c_uint,
+            buffer,
+        );
+    })
+}
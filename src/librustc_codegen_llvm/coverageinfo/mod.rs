@@ -0,0 +1,114 @@
+//! Code coverage instrumentation for `-Z instrument-coverage`.
+//!
+//! The counters injected by the `InstrumentCoverage` MIR pass become calls to
+//! `llvm.instrprof.increment`, which LLVM's `instrprof` pass lowers to updates
+//! of the counters of the profiler runtime. The regions of code they cover
+//! are collected per codegen unit, and written to its coverage map by
+//! `mapgen` once all of its functions were generated.
+
+use crate::builder::Builder;
+use crate::common::CodegenCx;
+use crate::llvm;
+use crate::value::Value;
+
+use log::debug;
+use rustc::ty::Instance;
+use rustc::util::nodemap::FxHashMap;
+use rustc_codegen_ssa::traits::*;
+use syntax_pos::Span;
+
+use std::cell::RefCell;
+use std::ffi::CString;
+
+mod mapgen;
+
+/// The coverage counters of the functions of a codegen unit.
+pub struct CrateCoverageContext<'ll, 'tcx> {
+    function_coverage_map: RefCell<FxHashMap<Instance<'tcx>, FunctionCoverage>>,
+    pgo_func_name_var_map: RefCell<FxHashMap<Instance<'tcx>, &'ll Value>>,
+}
+
+impl CrateCoverageContext<'ll, 'tcx> {
+    pub fn new() -> Self {
+        CrateCoverageContext {
+            function_coverage_map: Default::default(),
+            pgo_func_name_var_map: Default::default(),
+        }
+    }
+
+    fn take_function_coverage_map(&self) -> FxHashMap<Instance<'tcx>, FunctionCoverage> {
+        self.function_coverage_map.replace(FxHashMap::default())
+    }
+}
+
+/// The counters of a function, and the regions of code they cover.
+struct FunctionCoverage {
+    source_hash: u64,
+    /// The region of every counter, by index. Counters whose code was never
+    /// generated have no region.
+    regions: Vec<Option<Span>>,
+}
+
+impl CoverageInfoMethods for CodegenCx<'ll, 'tcx> {
+    fn coverageinfo_finalize(&self) {
+        if self.coverage_cx.is_some() {
+            mapgen::finalize(self);
+        }
+    }
+}
+
+impl CoverageInfoBuilderMethods<'tcx> for Builder<'a, 'll, 'tcx> {
+    fn add_counter_region(
+        &mut self,
+        instance: Instance<'tcx>,
+        function_source_hash: u64,
+        num_counters: u32,
+        index: u32,
+        region: Span,
+    ) {
+        debug!(
+            "adding counter {} of {} to {:?}, covering {:?}",
+            index, num_counters, instance, region
+        );
+        let coverage_cx = self.coverage_cx.as_ref().unwrap();
+        coverage_cx
+            .function_coverage_map
+            .borrow_mut()
+            .entry(instance)
+            .or_insert_with(|| FunctionCoverage {
+                source_hash: function_source_hash,
+                regions: vec![None; num_counters as usize],
+            })
+            .regions[index as usize] = Some(region);
+
+        let fn_name = self.cx.get_pgo_func_name_var(instance);
+        let args = [
+            fn_name,
+            self.const_u64(function_source_hash),
+            self.const_u32(num_counters),
+            self.const_u32(index),
+        ];
+        let llfn = self.cx.get_intrinsic("llvm.instrprof.increment");
+        self.call(llfn, &args, None);
+    }
+}
+
+impl CodegenCx<'ll, 'tcx> {
+    /// The variable holding the name under which the counters of `instance`
+    /// are recorded in the profile.
+    fn get_pgo_func_name_var(&self, instance: Instance<'tcx>) -> &'ll Value {
+        let coverage_cx = self.coverage_cx.as_ref().unwrap();
+        if let Some(&name_var) = coverage_cx.pgo_func_name_var_map.borrow().get(&instance) {
+            return name_var;
+        }
+
+        let llfn = self.get_fn(instance);
+        let name = CString::new(&*self.tcx.symbol_name(instance).name.as_str()).unwrap();
+        let name_var = unsafe {
+            let name_var = llvm::LLVMRustCoverageCreatePGOFuncNameVar(llfn, name.as_ptr());
+            llvm::LLVMConstPointerCast(name_var, self.type_i8p())
+        };
+        coverage_cx.pgo_func_name_var_map.borrow_mut().insert(instance, name_var);
+        name_var
+    }
+}
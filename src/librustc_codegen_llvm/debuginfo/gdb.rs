@@ -6,12 +6,15 @@ use crate::builder::Builder;
 use crate::common::CodegenCx;
 use crate::value::Value;
 use rustc::bug;
-use rustc::session::config::DebugInfo;
+use rustc::hir::def_id::LOCAL_CRATE;
+use rustc::session::config::{CrateType, DebugInfo};
 use rustc_codegen_ssa::traits::*;
 
 use syntax::attr;
 use syntax::symbol::sym;
 
+use std::iter;
+
 /// Inserts a side-effect free instruction sequence that makes sure that the
 /// .debug_gdb_scripts global is referenced, so it isn't removed by the linker.
 pub fn insert_reference_to_gdb_debug_scripts_section_global(bx: &mut Builder<'_, '_, '_>) {
@@ -39,7 +42,7 @@ pub fn get_or_insert_gdb_debug_scripts_section_global(cx: &CodegenCx<'ll, '_>) -
 
     section_var.unwrap_or_else(|| {
         let section_name = b".debug_gdb_scripts\0";
-        let section_contents = b"\x01gdb_load_rust_pretty_printers.py\0";
+        let section_contents = gdb_debug_scripts_section_contents(cx);
 
         unsafe {
             let llvm_type = cx.type_array(cx.type_i8(), section_contents.len() as u64);
@@ -48,7 +51,7 @@ pub fn get_or_insert_gdb_debug_scripts_section_global(cx: &CodegenCx<'ll, '_>) -
                 .define_global(section_var_name, llvm_type)
                 .unwrap_or_else(|| bug!("symbol `{}` is already defined", section_var_name));
             llvm::LLVMSetSection(section_var, section_name.as_ptr().cast());
-            llvm::LLVMSetInitializer(section_var, cx.const_bytes(section_contents));
+            llvm::LLVMSetInitializer(section_var, cx.const_bytes(&section_contents));
             llvm::LLVMSetGlobalConstant(section_var, llvm::True);
             llvm::LLVMSetUnnamedAddr(section_var, llvm::True);
             llvm::LLVMRustSetLinkage(section_var, llvm::Linkage::LinkOnceODRLinkage);
@@ -60,11 +63,46 @@ pub fn get_or_insert_gdb_debug_scripts_section_global(cx: &CodegenCx<'ll, '_>) -
     })
 }
 
+/// Returns the entries of the .debug_gdb_scripts section: a reference to the
+/// generic pretty-printers, followed by the inline `#[debugger_visualizer]`
+/// scripts of this crate and of all the crates it links.
+fn gdb_debug_scripts_section_contents(cx: &CodegenCx<'ll, '_>) -> Vec<u8> {
+    let mut contents = b"\x01gdb_load_rust_pretty_printers.py\0".to_vec();
+
+    let tcx = cx.tcx;
+    for cnum in iter::once(LOCAL_CRATE).chain(tcx.crates().iter().cloned()) {
+        for visualizer in tcx.debugger_visualizers(cnum).iter() {
+            // Inline Python scripts start with a line holding their name.
+            contents.push(4);
+            contents.extend_from_slice(tcx.crate_name(cnum).as_str().as_bytes());
+            contents.extend_from_slice(b"::");
+            contents.extend_from_slice(visualizer.name.as_bytes());
+            contents.push(b'\n');
+            contents.extend_from_slice(&visualizer.src);
+            contents.push(0);
+        }
+    }
+
+    contents
+}
+
 pub fn needs_gdb_debug_scripts_section(cx: &CodegenCx<'_, '_>) -> bool {
     let omit_gdb_pretty_printer_section =
         attr::contains_name(&cx.tcx.hir().krate_attrs(), sym::omit_gdb_pretty_printer_section);
 
+    // The section only goes into the artifacts a debugger loads. Rust
+    // libraries get theirs from the crate linking them, which also embeds
+    // their visualizers, as the linker keeps a single copy of the section.
+    let is_linked_output =
+        cx.sess().crate_types.borrow().iter().any(|&crate_type| match crate_type {
+            CrateType::Executable | CrateType::Dylib | CrateType::Cdylib | CrateType::Staticlib => {
+                true
+            }
+            CrateType::Rlib | CrateType::ProcMacro => false,
+        });
+
     !omit_gdb_pretty_printer_section
+        && is_linked_output
         && cx.sess().opts.debuginfo != DebugInfo::None
         && cx.sess().target.target.options.emit_debug_gdb_scripts
 }
@@ -26,7 +26,7 @@ use rustc::hir::CodegenFnAttrFlags;
 use rustc::ich::NodeIdHashingMode;
 use rustc::mir::interpret::truncate;
 use rustc::mir::{self, Field, GeneratorLayout};
-use rustc::session::config::{self, DebugInfo};
+use rustc::session::config::{self, DebugInfo, SplitDebuginfo};
 use rustc::ty::layout::{
     self, Align, Integer, IntegerExt, LayoutOf, PrimitiveExt, Size, TyLayout, VariantIdx,
 };
@@ -396,6 +396,7 @@ fn vec_slice_metadata(
             align: pointer_align,
             flags: DIFlags::FlagZero,
             discriminant: None,
+            source_info: None,
         },
         MemberDescription {
             name: "length".to_owned(),
@@ -405,6 +406,7 @@ fn vec_slice_metadata(
             align: usize_align,
             flags: DIFlags::FlagZero,
             discriminant: None,
+            source_info: None,
         },
     ];
 
@@ -513,6 +515,7 @@ fn trait_pointer_metadata(
             align: data_ptr_field.align.abi,
             flags: DIFlags::FlagArtificial,
             discriminant: None,
+            source_info: None,
         },
         MemberDescription {
             name: "vtable".to_owned(),
@@ -522,6 +525,7 @@ fn trait_pointer_metadata(
             align: vtable_field.align.abi,
             flags: DIFlags::FlagArtificial,
             discriminant: None,
+            source_info: None,
         },
     ];
 
@@ -893,7 +897,14 @@ pub fn compile_unit_metadata(
     let work_dir = SmallCStr::new(&tcx.sess.working_dir.0.to_string_lossy());
     let producer = CString::new(producer).unwrap();
     let flags = "\0";
-    let split_name = "\0";
+    // With split debuginfo the skeleton unit left in the object file refers to
+    // the `.dwo` file that LLVM writes next to it.
+    let split_name = if tcx.sess.split_debuginfo() != SplitDebuginfo::Off {
+        let dwo_path = tcx.output_filenames(LOCAL_CRATE).temp_path_dwo(Some(codegen_unit_name));
+        path_to_c_string(&dwo_path)
+    } else {
+        CString::default()
+    };
 
     // FIXME(#60020):
     //
@@ -1002,6 +1013,13 @@ impl MetadataCreationResult<'ll> {
     }
 }
 
+/// The location a type member is declared at.
+#[derive(Copy, Clone, Debug)]
+struct SourceInfo<'ll> {
+    file: &'ll DIFile,
+    line: c_uint,
+}
+
 /// Description of a type member, which can either be a regular field (as in
 /// structs or tuples) or an enum variant.
 #[derive(Debug)]
@@ -1013,6 +1031,7 @@ struct MemberDescription<'ll> {
     align: Align,
     flags: DIFlags,
     discriminant: Option<u64>,
+    source_info: Option<SourceInfo<'ll>>,
 }
 
 impl<'ll> MemberDescription<'ll> {
@@ -1022,13 +1041,17 @@ impl<'ll> MemberDescription<'ll> {
         composite_type_metadata: &'ll DIScope,
     ) -> &'ll DIType {
         let member_name = CString::new(self.name).unwrap();
+        let (file, line) = self
+            .source_info
+            .map(|info| (info.file, info.line))
+            .unwrap_or_else(|| (unknown_file_metadata(cx), UNKNOWN_LINE_NUMBER));
         unsafe {
             llvm::LLVMRustDIBuilderCreateVariantMemberType(
                 DIB(cx),
                 composite_type_metadata,
                 member_name.as_ptr(),
-                unknown_file_metadata(cx),
-                UNKNOWN_LINE_NUMBER,
+                file,
+                line,
                 self.size.bits(),
                 self.align.bits() as u32,
                 self.offset.bits(),
@@ -1100,6 +1123,7 @@ impl<'tcx> StructMemberDescriptionFactory<'tcx> {
                     align: field.align.abi,
                     flags: DIFlags::FlagZero,
                     discriminant: None,
+                    source_info: None,
                 }
             })
             .collect()
@@ -1161,6 +1185,7 @@ impl<'tcx> TupleMemberDescriptionFactory<'tcx> {
                     align,
                     flags: DIFlags::FlagZero,
                     discriminant: None,
+                    source_info: None,
                 }
             })
             .collect()
@@ -1220,6 +1245,7 @@ impl<'tcx> UnionMemberDescriptionFactory<'tcx> {
                     align: field.align.abi,
                     flags: DIFlags::FlagZero,
                     discriminant: None,
+                    source_info: None,
                 }
             })
             .collect()
@@ -1338,10 +1364,11 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
 
         let variant_info_for = |index: VariantIdx| match self.enum_type.kind {
             ty::Adt(adt, _) => VariantInfo::Adt(&adt.variants[index]),
-            ty::Generator(_, substs, _) => {
+            ty::Generator(def_id, substs, _) => {
                 let (generator_layout, generator_saved_local_names) =
                     generator_variant_info_data.as_ref().unwrap();
                 VariantInfo::Generator {
+                    def_id,
                     substs,
                     generator_layout: *generator_layout,
                     generator_saved_local_names,
@@ -1393,6 +1420,7 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
                     align: self.layout.align.abi,
                     flags: DIFlags::FlagZero,
                     discriminant: None,
+                    source_info: variant_info.source_info(cx),
                 }]
             }
             layout::Variants::Multiple {
@@ -1449,6 +1477,7 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
                                 self.layout.ty.discriminant_for_variant(cx.tcx, i).unwrap().val
                                     as u64,
                             ),
+                            source_info: variant_info.source_info(cx),
                         }
                     })
                     .collect()
@@ -1527,6 +1556,7 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
                         align: variant.align.abi,
                         flags: DIFlags::FlagZero,
                         discriminant: None,
+                        source_info: None,
                     }]
                 } else {
                     variants
@@ -1576,6 +1606,7 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
                                 align: self.layout.align.abi,
                                 flags: DIFlags::FlagZero,
                                 discriminant: niche_value,
+                                source_info: variant_info.source_info(cx),
                             }
                         })
                         .collect()
@@ -1618,6 +1649,7 @@ impl VariantMemberDescriptionFactory<'ll, 'tcx> {
                     align,
                     flags: DIFlags::FlagZero,
                     discriminant: None,
+                    source_info: None,
                 }
             })
             .collect()
@@ -1635,6 +1667,7 @@ enum EnumDiscriminantInfo<'ll> {
 enum VariantInfo<'a, 'tcx> {
     Adt(&'tcx ty::VariantDef),
     Generator {
+        def_id: DefId,
         substs: SubstsRef<'tcx>,
         generator_layout: &'tcx GeneratorLayout<'tcx>,
         generator_saved_local_names: &'a IndexVec<mir::GeneratorSavedLocal, Option<ast::Name>>,
@@ -1683,6 +1716,26 @@ impl<'tcx> VariantInfo<'_, 'tcx> {
         };
         field_name.map(|name| name.to_string()).unwrap_or_else(|| format!("__{}", i))
     }
+
+    /// Generator states point at the `yield` they are suspended at (or the
+    /// start or end of the generator body), so a debugger can tell the user
+    /// where a suspended generator will resume.
+    fn source_info<'ll>(&self, cx: &CodegenCx<'ll, 'tcx>) -> Option<SourceInfo<'ll>> {
+        match self {
+            VariantInfo::Generator { def_id, generator_layout, variant_index, .. } => {
+                let span = generator_layout.variant_source_info[*variant_index].span;
+                if span.is_dummy() {
+                    return None;
+                }
+                let loc = span_start(cx, span);
+                Some(SourceInfo {
+                    file: file_metadata(cx, &loc.file.name, def_id.krate),
+                    line: loc.line as c_uint,
+                })
+            }
+            VariantInfo::Adt(_) => None,
+        }
+    }
 }
 
 /// Returns a tuple of (1) `type_metadata_stub` of the variant, (2) a
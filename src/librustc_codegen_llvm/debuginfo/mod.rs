@@ -23,7 +23,7 @@ use crate::common::CodegenCx;
 use crate::value::Value;
 use rustc::mir;
 use rustc::session::config::{self, DebugInfo};
-use rustc::ty::{self, Instance, InstanceDef, ParamEnv, Ty};
+use rustc::ty::{self, Instance, InstanceDef, ParamEnv, Ty, TypeFoldable};
 use rustc::util::nodemap::{DefIdMap, FxHashMap, FxHashSet};
 use rustc_codegen_ssa::debuginfo::type_names;
 use rustc_codegen_ssa::mir::debuginfo::{DebugScope, FunctionDebugContext, VariableKind};
@@ -443,6 +443,13 @@ impl DebugInfoMethods<'tcx> for CodegenCx<'ll, 'tcx> {
                     name_to_append_suffix_to.push_str(",");
                 }
 
+                // Parameters that were left in place by polymorphization have no
+                // actual type, use the name of the parameter instead.
+                if let ty::Param(param) = actual_type.kind {
+                    name_to_append_suffix_to.push_str(&param.name.as_str());
+                    continue;
+                }
+
                 let actual_type =
                     cx.tcx.normalize_erasing_regions(ParamEnv::reveal_all(), actual_type);
                 // Add actual type name to <...> clause of function name
@@ -459,6 +466,9 @@ impl DebugInfoMethods<'tcx> for CodegenCx<'ll, 'tcx> {
                     .zip(names)
                     .filter_map(|(kind, name)| {
                         if let GenericArgKind::Type(ty) = kind.unpack() {
+                            if let ty::Param(_) = ty.kind {
+                                return None;
+                            }
                             let actual_type =
                                 cx.tcx.normalize_erasing_regions(ParamEnv::reveal_all(), ty);
                             let actual_type_metadata =
@@ -513,8 +523,10 @@ impl DebugInfoMethods<'tcx> for CodegenCx<'ll, 'tcx> {
 
                     // Only "class" methods are generally understood by LLVM,
                     // so avoid methods on other types (e.g., `<*mut T>::null`).
+                    // Polymorphized methods whose self type still mentions
+                    // generic parameters have no metadata to attach to either.
                     match impl_self_ty.kind {
-                        ty::Adt(def, ..) if !def.is_box() => {
+                        ty::Adt(def, ..) if !def.is_box() && !impl_self_ty.has_param_types() => {
                             Some(type_metadata(cx, impl_self_ty, syntax_pos::DUMMY_SP))
                         }
                         _ => None,
@@ -25,6 +25,8 @@ use rustc_codegen_ssa::traits::*;
 
 use syntax_pos::Span;
 
+use libc::c_uint;
+
 use std::cmp::Ordering;
 use std::{i128, iter, u128};
 
@@ -805,6 +807,15 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
+    fn type_test(&mut self, pointer: Self::Value, typeid: &str) -> Self::Value {
+        let type_test = self.get_intrinsic("llvm.type.test");
+        let pointer = self.pointercast(pointer, self.type_i8p());
+        let typeid = unsafe {
+            llvm::LLVMMDStringInContext(self.llcx, typeid.as_ptr().cast(), typeid.len() as c_uint)
+        };
+        self.call(type_test, &[pointer, typeid], None)
+    }
+
     fn va_start(&mut self, va_list: &'ll Value) -> &'ll Value {
         let intrinsic = self.cx().get_intrinsic("llvm.va_start");
         self.call(intrinsic, &[va_list], None)
@@ -32,6 +32,7 @@ use rustc_codegen_ssa::CompiledModule;
 use rustc_errors::{FatalError, Handler};
 use std::any::Any;
 use std::ffi::CStr;
+use std::path::Path;
 use std::sync::Arc;
 use syntax::expand::allocator::AllocatorKind;
 
@@ -48,6 +49,7 @@ mod back {
     pub mod archive;
     pub mod bytecode;
     pub mod lto;
+    pub mod stack_sizes;
     pub mod write;
 }
 
@@ -61,6 +63,7 @@ mod callee;
 mod common;
 mod consts;
 mod context;
+mod coverageinfo;
 mod debuginfo;
 mod declare;
 mod intrinsic;
@@ -187,6 +190,9 @@ impl WriteBackendMethods for LlvmCodegenBackend {
     ) {
         back::lto::run_pass_manager(cgcx, module, config, thin)
     }
+    fn read_stack_sizes(sess: &Session, object: &Path) -> Option<Vec<(String, u64)>> {
+        back::stack_sizes::read_stack_sizes(sess, object)
+    }
 }
 
 unsafe impl Send for LlvmCodegenBackend {} // Llvm is on a per-thread basis
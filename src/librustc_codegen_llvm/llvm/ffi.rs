@@ -116,6 +116,7 @@ pub enum Attribute {
     NonLazyBind = 23,
     OptimizeNone = 24,
     ReturnsTwice = 25,
+    SanitizeHWAddress = 26,
 }
 
 /// LLVMIntPredicate
@@ -472,6 +473,22 @@ pub enum PassKind {
     Module,
 }
 
+/// LLVMRustCounterMappingRegion
+///
+/// A region of source code covered by a coverage counter. Lines and columns
+/// start at 1, and `file_id` is an index into the virtual file mapping of the
+/// function.
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct CounterMappingRegion {
+    pub counter_id: u32,
+    pub file_id: u32,
+    pub line_start: u32,
+    pub column_start: u32,
+    pub line_end: u32,
+    pub column_end: u32,
+}
+
 /// LLVMRustThinLTOData
 extern "C" {
     pub type ThinLTOData;
@@ -551,6 +568,10 @@ extern "C" {
 }
 #[repr(C)]
 pub struct SectionIterator<'a>(InvariantOpaque<'a>);
+#[repr(C)]
+pub struct RelocationIterator<'a>(InvariantOpaque<'a>);
+#[repr(C)]
+pub struct SymbolIterator<'a>(InvariantOpaque<'a>);
 extern "C" {
     pub type Pass;
 }
@@ -1507,6 +1528,38 @@ extern "C" {
     /// Returns the current section contents as a string buffer.
     pub fn LLVMGetSectionContents(SI: &SectionIterator<'_>) -> *const c_char;
 
+    /// Enumerates the relocations in a section.
+    pub fn LLVMGetRelocations(SI: &SectionIterator<'a>) -> &'a mut RelocationIterator<'a>;
+    /// Destroys a relocation iterator.
+    pub fn LLVMDisposeRelocationIterator(RI: &'a mut RelocationIterator<'a>);
+    /// Returns `true` if the relocation iterator is at the end of the
+    /// relocations of the given section.
+    pub fn LLVMIsRelocationIteratorAtEnd(
+        SI: &SectionIterator<'a>,
+        RI: &RelocationIterator<'a>,
+    ) -> Bool;
+    /// Moves the relocation iterator to point to the next relocation.
+    pub fn LLVMMoveToNextRelocation(RI: &RelocationIterator<'_>);
+    /// Returns the offset of the current relocation within its section.
+    pub fn LLVMGetRelocationOffset(RI: &RelocationIterator<'_>) -> u64;
+    /// Returns an iterator pointing to the symbol of the current relocation.
+    pub fn LLVMGetRelocationSymbol(RI: &RelocationIterator<'a>) -> &'a mut SymbolIterator<'a>;
+    /// Destroys a symbol iterator.
+    pub fn LLVMDisposeSymbolIterator(SI: &'a mut SymbolIterator<'a>);
+    /// Enumerates the symbols in an object file.
+    pub fn LLVMGetSymbols(ObjFile: &'a ObjectFile) -> &'a mut SymbolIterator<'a>;
+    /// Returns `true` if the symbol iterator is at the end of the symbol list.
+    pub fn LLVMIsSymbolIteratorAtEnd(ObjFile: &'a ObjectFile, SI: &SymbolIterator<'a>) -> Bool;
+    /// Moves the symbol iterator to point to the next symbol.
+    pub fn LLVMMoveToNextSymbol(SI: &SymbolIterator<'_>);
+    /// Returns the name of the current symbol.
+    pub fn LLVMGetSymbolName(SI: &SymbolIterator<'_>) -> *const c_char;
+    /// Returns the address of the current symbol, which in a relocatable
+    /// object file is its offset in its section.
+    pub fn LLVMGetSymbolAddress(SI: &SymbolIterator<'_>) -> u64;
+    /// Moves the section iterator to the section containing the current symbol.
+    pub fn LLVMMoveToContainingSection(Sect: &SectionIterator<'a>, Sym: &SymbolIterator<'a>);
+
     /// Reads the given file and returns it as a memory buffer. Use
     /// LLVMDisposeMemoryBuffer() to get rid of it.
     pub fn LLVMRustCreateMemoryBufferWithContentsOfFile(
@@ -1819,8 +1872,15 @@ extern "C" {
 
     pub fn LLVMRustPassKind(Pass: &Pass) -> PassKind;
     pub fn LLVMRustFindAndCreatePass(Pass: *const c_char) -> Option<&'static mut Pass>;
-    pub fn LLVMRustCreateAddressSanitizerFunctionPass(Recover: bool) -> &'static mut Pass;
-    pub fn LLVMRustCreateModuleAddressSanitizerPass(Recover: bool) -> &'static mut Pass;
+    pub fn LLVMRustCreateAddressSanitizerFunctionPass(
+        CompileKernel: bool,
+        Recover: bool,
+    ) -> &'static mut Pass;
+    pub fn LLVMRustCreateModuleAddressSanitizerPass(
+        CompileKernel: bool,
+        Recover: bool,
+    ) -> &'static mut Pass;
+    pub fn LLVMRustCreateHWAddressSanitizerPass(Recover: bool) -> &'static mut Pass;
     pub fn LLVMRustCreateMemorySanitizerPass(
         TrackOrigins: c_int,
         Recover: bool,
@@ -1884,6 +1944,7 @@ extern "C" {
         PM: &PassManager<'a>,
         M: &'a Module,
         Output: *const c_char,
+        DwoOutput: *const c_char,
         FileType: FileType,
     ) -> LLVMRustResult;
     pub fn LLVMRustPrintModule(
@@ -1915,6 +1976,10 @@ extern "C" {
         SI: &SectionIterator<'_>,
         data: &mut Option<std::ptr::NonNull<c_char>>,
     ) -> size_t;
+    pub fn LLVMRustGetSectionIndex(SI: &SectionIterator<'_>) -> u64;
+    pub fn LLVMRustGetRelocatedSectionIndex(SI: &SectionIterator<'_>) -> u64;
+    pub fn LLVMRustGetRelocationAddend(RI: &RelocationIterator<'_>) -> i64;
+    pub fn LLVMRustIsFunctionSymbol(SI: &SymbolIterator<'_>) -> bool;
 
     #[allow(improper_ctypes)]
     pub fn LLVMRustWriteTwineToString(T: &Twine, s: &RustString);
@@ -1983,6 +2048,32 @@ extern "C" {
 
     pub fn LLVMRustSetComdat(M: &'a Module, V: &'a Value, Name: *const c_char, NameLen: size_t);
     pub fn LLVMRustUnsetComdat(V: &Value);
+    pub fn LLVMRustAddTypeMetadata(
+        V: &Value,
+        Offset: u64,
+        TypeId: *const c_char,
+        TypeIdLen: size_t,
+    );
+
+    pub fn LLVMRustCoverageWriteFilenamesSectionToBuffer(
+        Filenames: *const *const c_char,
+        FilenamesLen: size_t,
+        BufferOut: &RustString,
+    );
+    pub fn LLVMRustCoverageWriteMappingToBuffer(
+        VirtualFileMappingIDs: *const c_uint,
+        NumVirtualFileMappingIDs: c_uint,
+        Regions: *const CounterMappingRegion,
+        NumRegions: c_uint,
+        BufferOut: &RustString,
+    );
+    pub fn LLVMRustCoverageCreatePGOFuncNameVar(F: &'a Value, FuncName: *const c_char)
+    -> &'a Value;
+    pub fn LLVMRustCoverageComputeHash(Name: *const c_char) -> u64;
+    pub fn LLVMRustCoverageWriteSectionNameToString(M: &Module, Str: &RustString);
+    pub fn LLVMRustCoverageWriteMappingVarNameToString(Str: &RustString);
+    pub fn LLVMRustCoverageMappingVersion() -> u32;
+
     pub fn LLVMRustSetModulePICLevel(M: &Module);
     pub fn LLVMRustSetModulePIELevel(M: &Module);
     pub fn LLVMRustModuleBufferCreate(M: &Module) -> &'static mut ModuleBuffer;
@@ -105,6 +105,14 @@ pub fn UnsetComdat(val: &'a Value) {
     }
 }
 
+/// Marks `offset` bytes into the function or global `val` as a member of the
+/// type `typeid`, which `llvm.type.test` checks for.
+pub fn add_type_metadata(val: &'a Value, offset: u64, typeid: &str) {
+    unsafe {
+        LLVMRustAddTypeMetadata(val, offset, typeid.as_ptr().cast(), typeid.len());
+    }
+}
+
 pub fn SetUnnamedAddr(global: &'a Value, unnamed: bool) {
     unsafe {
         LLVMSetUnnamedAddr(global, unnamed as Bool);
@@ -188,6 +196,51 @@ pub fn mk_section_iter(llof: &'a ffi::ObjectFile) -> SectionIter<'a> {
     unsafe { SectionIter { llsi: LLVMGetSections(llof) } }
 }
 
+// Memory-managed interface to relocation iterators.
+
+pub struct RelocationIter<'a> {
+    pub llri: &'a mut RelocationIterator<'a>,
+}
+
+impl Drop for RelocationIter<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeRelocationIterator(&mut *(self.llri as *mut _));
+        }
+    }
+}
+
+pub fn mk_relocation_iter(llsi: &SectionIterator<'a>) -> RelocationIter<'a> {
+    unsafe { RelocationIter { llri: LLVMGetRelocations(llsi) } }
+}
+
+// Memory-managed interface to symbol iterators.
+
+pub struct SymbolIter<'a> {
+    pub llsi: &'a mut SymbolIterator<'a>,
+}
+
+impl Drop for SymbolIter<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeSymbolIterator(&mut *(self.llsi as *mut _));
+        }
+    }
+}
+
+pub fn mk_symbol_iter(llof: &'a ffi::ObjectFile) -> SymbolIter<'a> {
+    unsafe { SymbolIter { llsi: LLVMGetSymbols(llof) } }
+}
+
+/// Returns an iterator pointing to the symbol the relocation at `llri` refers to.
+pub fn mk_relocation_symbol_iter(llri: &RelocationIterator<'a>) -> SymbolIter<'a> {
+    unsafe { SymbolIter { llsi: LLVMGetRelocationSymbol(llri) } }
+}
+
+pub fn get_symbol_name(llsi: &SymbolIterator<'_>) -> String {
+    unsafe { CStr::from_ptr(LLVMGetSymbolName(llsi)).to_string_lossy().into_owned() }
+}
+
 /// Safe wrapper around `LLVMGetParam`, because segfaults are no fun.
 pub fn get_param(llfn: &'a Value, index: c_uint) -> &'a Value {
     unsafe {
@@ -224,6 +277,12 @@ pub fn build_string(f: impl FnOnce(&RustString)) -> Result<String, FromUtf8Error
     String::from_utf8(sr.bytes.into_inner())
 }
 
+pub fn build_byte_buffer(f: impl FnOnce(&RustString)) -> Vec<u8> {
+    let sr = RustString { bytes: RefCell::new(Vec::new()) };
+    f(&sr);
+    sr.bytes.into_inner()
+}
+
 pub fn twine_to_string(tr: &Twine) -> String {
     unsafe {
         build_string(|s| LLVMRustWriteTwineToString(tr, s)).expect("got a non-UTF8 Twine from LLVM")
@@ -10,6 +10,7 @@ use rustc::mir::mono::{Linkage, Visibility};
 use rustc::ty::layout::{FnAbiExt, LayoutOf};
 use rustc::ty::{Instance, TypeFoldable};
 use rustc_codegen_ssa::traits::*;
+use rustc_codegen_utils::cfi;
 
 pub use rustc::mir::mono::MonoItem;
 
@@ -47,7 +48,10 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         visibility: Visibility,
         symbol_name: &str,
     ) {
-        assert!(!instance.substs.needs_infer() && !instance.substs.has_param_types());
+        assert!(!instance.substs.needs_infer());
+        assert!(
+            !instance.substs.has_param_types() || self.tcx.sess.opts.debugging_opts.polymorphize
+        );
 
         let fn_abi = FnAbi::of_instance(self, instance, &[]);
         let lldecl = self.declare_fn(symbol_name, &fn_abi);
@@ -79,6 +83,14 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
 
         attributes::from_fn_attrs(self, lldecl, instance, &fn_abi);
 
+        // Allow the function to be called through function pointers under
+        // control flow integrity checks.
+        if self.tcx.sess.emits_cfi_type_metadata() {
+            if let Some(sig) = cfi::instance_fn_ptr_sig(self.tcx, instance) {
+                llvm::add_type_metadata(lldecl, 0, &cfi::fn_ptr_typeid(self.tcx, sig));
+            }
+        }
+
         self.instances.borrow_mut().insert(instance, lldecl);
     }
 }
@@ -2,7 +2,7 @@ use rustc::hir::def_id::CrateNum;
 use rustc::middle::cstore::{EncodedMetadata, LibSource, NativeLibrary, NativeLibraryKind};
 use rustc::middle::dependency_format::Linkage;
 use rustc::session::config::{
-    self, DebugInfo, OutputFilenames, OutputType, PrintRequest, Sanitizer,
+    self, DebugInfo, OutputFilenames, OutputType, PrintRequest, Sanitizer, SplitDebuginfo,
 };
 use rustc::session::search_paths::PathKind;
 /// For all the linkers we support, and information they might
@@ -11,12 +11,13 @@ use rustc::session::{filesearch, Session};
 use rustc::util::common::{time, time_ext};
 use rustc_data_structures::fx::FxHashSet;
 use rustc_fs_util::fix_windows_verbatim_for_gcc;
-use rustc_target::spec::{LinkerFlavor, PanicStrategy, RelroLevel};
+use rustc_target::spec::{LinkerFlavor, LldFlavor, PanicStrategy, RelroLevel};
 use syntax::symbol::Symbol;
 
 use super::archive::ArchiveBuilder;
 use super::command::Command;
 use super::linker::Linker;
+use super::linker_diagnostics;
 use super::rpath::{self, RPathConfig};
 use crate::{
     looks_like_rust_object_file, CodegenResults, CrateInfo, METADATA_FILENAME,
@@ -128,6 +129,11 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
         for obj in codegen_results.modules.iter().filter_map(|m| m.bytecode_compressed.as_ref()) {
             remove(sess, obj);
         }
+        if !preserve_dwarf_objects(sess) {
+            for dwo in codegen_results.modules.iter().filter_map(|m| m.dwarf_object.as_ref()) {
+                remove(sess, dwo);
+            }
+        }
         if let Some(ref metadata_module) = codegen_results.metadata_module {
             if let Some(ref obj) = metadata_module.object {
                 remove(sess, obj);
@@ -557,6 +563,12 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
     if let Some(args) = sess.target.target.options.post_link_args.get(&flavor) {
         cmd.args(args);
     }
+    // Ask for the untranslated messages the linker errors are recognized by.
+    if sess.target.target.options.is_like_msvc {
+        cmd.env("VSLANG", "1033");
+    } else {
+        cmd.env("LC_ALL", "C");
+    }
     for &(ref k, ref v) in &sess.target.target.options.link_env {
         cmd.env(k, v);
     }
@@ -665,6 +677,12 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
         }
     }
 
+    if let Some(ref path) = sess.opts.debugging_opts.linker_log {
+        if let Err(e) = write_linker_log(path, &cmd, tmpdir) {
+            sess.err(&format!("failed to write linker log to `{}`: {}", path.display(), e));
+        }
+    }
+
     match prog {
         Ok(prog) => {
             fn escape_string(s: &[u8]) -> String {
@@ -677,14 +695,22 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
             if !prog.status.success() {
                 let mut output = prog.stderr.clone();
                 output.extend_from_slice(&prog.stdout);
+                let output = escape_string(&output);
+                let invocation = match sess.opts.debugging_opts.linker_log {
+                    Some(ref path) => {
+                        format!("the linker invocation was saved to `{}`", path.display())
+                    }
+                    None => format!("{:?}", &cmd),
+                };
                 sess.struct_err(&format!(
                     "linking with `{}` failed: {}",
                     pname.display(),
                     prog.status
                 ))
-                .note(&format!("{:?}", &cmd))
-                .note(&escape_string(&output))
+                .note(&invocation)
+                .note(&output)
                 .emit();
+                linker_diagnostics::report_linker_problems(sess, codegen_results, &output);
                 sess.abort_if_errors();
             }
             info!("linker stderr:\n{}", escape_string(&prog.stderr));
@@ -734,6 +760,62 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
             sess.fatal(&format!("failed to run dsymutil: {}", e))
         }
     }
+
+    // With packed split debuginfo the `.dwo` files referenced by the output
+    // are gathered into a single `.dwp` file next to it.
+    if sess.split_debuginfo() == SplitDebuginfo::Packed {
+        package_dwarf_objects(sess, out_filename);
+    }
+}
+
+/// Runs the DWARF packager over `out_filename`, writing `<out_filename>.dwp`.
+///
+/// The packager bundled in the sysroot is preferred, falling back to an
+/// `llvm-dwp` found in `PATH`.
+fn package_dwarf_objects(sess: &Session, out_filename: &Path) {
+    let bundled = format!("rust-llvm-dwp{}", env::consts::EXE_SUFFIX);
+    let dwp = sess
+        .host_filesearch(PathKind::All)
+        .get_tools_search_paths()
+        .into_iter()
+        .map(|dir| dir.join(&bundled))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| PathBuf::from("llvm-dwp"));
+
+    let mut dwp_filename = out_filename.as_os_str().to_owned();
+    dwp_filename.push(".dwp");
+
+    let mut cmd = Command::new(&dwp);
+    cmd.arg("-e").arg(out_filename).arg("-o").arg(&dwp_filename);
+    info!("{:?}", &cmd);
+    match cmd.output() {
+        Ok(prog) => {
+            if !prog.status.success() {
+                sess.struct_err(&format!(
+                    "packaging split debuginfo with `{}` failed: {}",
+                    dwp.display(),
+                    prog.status
+                ))
+                .note(&format!("{:?}", &cmd))
+                .note(&String::from_utf8_lossy(&prog.stderr))
+                .emit();
+            }
+        }
+        Err(e) => sess.fatal(&format!("failed to run `{}`: {}", dwp.display(), e)),
+    }
+}
+
+/// Returns whether the `.dwo` files of this crate must outlive the compilation.
+///
+/// Only a packed executable no longer refers to them once it has been linked;
+/// anything else (unpacked output, rlibs and staticlibs linked later on) still
+/// needs them to be around for debuggers or for downstream packaging.
+fn preserve_dwarf_objects(sess: &Session) -> bool {
+    sess.split_debuginfo() != SplitDebuginfo::Packed
+        || !sess.opts.output_types.contains_key(&OutputType::Exe)
+        || sess.crate_types.borrow().iter().any(|&crate_type| {
+            crate_type == config::CrateType::Rlib || crate_type == config::CrateType::Staticlib
+        })
 }
 
 /// Returns a boolean indicating whether the specified crate should be ignored
@@ -810,6 +892,12 @@ pub fn linker_and_flavor(sess: &Session) -> (PathBuf, LinkerFlavor) {
         }
     }
 
+    // A self-contained link doesn't depend on a system toolchain, it always uses the `rust-lld`
+    // shipped in the sysroot, which `get_linker` puts on the `PATH` of the linker.
+    if sess.opts.debugging_opts.link_self_contained {
+        return (PathBuf::from("rust-lld"), LinkerFlavor::Lld(LldFlavor::Ld));
+    }
+
     // linker and linker flavor specified via command line have precedence over what the target
     // specification specifies
     if let Some(ret) = infer_from(sess, sess.opts.cg.linker.clone(), sess.opts.cg.linker_flavor) {
@@ -1081,6 +1169,27 @@ pub fn exec_linker(
     }
 }
 
+/// Saves the linker invocation for `-Z linker-log`, including the response
+/// file `exec_linker` may have fallen back to, which is deleted along with
+/// `tmpdir` once linking is done.
+fn write_linker_log(path: &Path, cmd: &Command, tmpdir: &Path) -> io::Result<()> {
+    let mut log = format!("{:?}\n", cmd);
+    let file = tmpdir.join("linker-arguments");
+    if file.exists() {
+        let bytes = fs::read(&file)?;
+        // The response files of link.exe are UTF-16 with a byte order mark.
+        let args = if bytes.starts_with(&[0xFF, 0xFE]) {
+            let units: Vec<u16> =
+                bytes[2..].chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+        log.push_str(&format!("\nresponse file `{}`:\n{}", file.display(), args));
+    }
+    fs::write(path, log)
+}
+
 fn link_args<'a, B: ArchiveBuilder<'a>>(
     cmd: &mut dyn Linker,
     flavor: LinkerFlavor,
@@ -1162,7 +1271,10 @@ fn link_args<'a, B: ArchiveBuilder<'a>>(
             // recent versions of gcc can be configured to generate position
             // independent executables by default. We have to pass -no-pie to
             // explicitly turn that off. Not applicable to ld.
-            if sess.target.target.options.linker_is_gnu && flavor != LinkerFlavor::Ld {
+            if sess.target.target.options.linker_is_gnu
+                && flavor != LinkerFlavor::Ld
+                && flavor != LinkerFlavor::Lld(LldFlavor::Ld)
+            {
                 cmd.no_position_independent_executable();
             }
         }
@@ -1240,7 +1352,7 @@ fn link_args<'a, B: ArchiveBuilder<'a>>(
         cmd.build_static_executable();
     }
 
-    if sess.opts.cg.profile_generate.enabled() {
+    if sess.opts.cg.profile_generate.enabled() || sess.opts.debugging_opts.instrument_coverage {
         cmd.pgo_gen();
     }
 
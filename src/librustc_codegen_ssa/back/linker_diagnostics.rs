@@ -0,0 +1,244 @@
+//! Structured diagnostics for the output of a failed linker invocation.
+//!
+//! The output of the linker is searched for the most common causes of a failed
+//! link, undefined symbols and native libraries that couldn't be found, as
+//! reported by GNU ld, lld, ld64 and link.exe. Undefined symbols are demangled
+//! and attributed to the crates whose object files reference them, and missing
+//! libraries are pointed at the `#[link]` attribute or `-l` flag that requested
+//! them. Everything else is only part of the raw linker output.
+
+use crate::CodegenResults;
+
+use rustc::hir::def_id::{CrateNum, LOCAL_CRATE};
+use rustc::middle::cstore::NativeLibrary;
+use rustc::session::Session;
+use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, PartialEq)]
+enum LinkerProblem<'a> {
+    /// A symbol that isn't defined anywhere, and the object file or codegen
+    /// unit referencing it, if the linker says which one.
+    UndefinedSymbol { symbol: &'a str, referenced_by: Option<&'a str> },
+    /// A library requested with `-l` that isn't in any of the search paths.
+    MissingLibrary { name: &'a str },
+}
+
+/// Returns the text following `pattern` in `line`, if it contains `pattern`.
+fn after<'a>(line: &'a str, pattern: &str) -> Option<&'a str> {
+    line.find(pattern).map(|i| &line[i + pattern.len()..])
+}
+
+/// Removes the quotes GNU ld puts around names, which depend on the locale.
+fn unquote(name: &str) -> &str {
+    name.trim_start_matches(|c| c == '`' || c == '\'' || c == '‘')
+        .trim_end_matches(|c| c == '\'' || c == '’')
+}
+
+fn parse_linker_output(output: &str) -> Vec<LinkerProblem<'_>> {
+    let mut problems = vec![];
+    // The object file GNU ld reported the function of, which the following
+    // undefined references are in.
+    let mut gnu_object = None;
+    // The undefined symbol lld or ld64 lists the references of in the
+    // following lines.
+    let mut listed_symbol = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        // lld: `>>>               foo.foo.7rcbfp3g-cgu.0.rcgu.o:(foo::main::h0123)`
+        // ld64: `      foo::main::h0123 in foo.foo.7rcbfp3g-cgu.0.rcgu.o`
+        if let Some(symbol) = listed_symbol {
+            if let Some(reference) = after(trimmed, ">>>") {
+                if let Some(end) = reference.find(":(") {
+                    let referenced_by = Some(reference[..end].trim());
+                    problems.push(LinkerProblem::UndefinedSymbol { symbol, referenced_by });
+                }
+                continue;
+            }
+            if line.starts_with(char::is_whitespace) && !trimmed.starts_with('"') {
+                if let Some(i) = trimmed.rfind(" in ") {
+                    let referenced_by = Some(&trimmed[i + " in ".len()..]);
+                    problems.push(LinkerProblem::UndefinedSymbol { symbol, referenced_by });
+                    continue;
+                }
+            }
+            push_unreferenced_symbol(&mut problems, symbol);
+            listed_symbol = None;
+        }
+
+        // lld: `ld.lld: error: undefined symbol: foo`
+        if let Some(symbol) = after(trimmed, "undefined symbol: ") {
+            listed_symbol = Some(symbol.trim());
+        // ld64: `  "_foo", referenced from:`
+        } else if trimmed.starts_with('"') && trimmed.ends_with("\", referenced from:") {
+            let symbol = &trimmed[1..trimmed.len() - "\", referenced from:".len()];
+            // Symbols of Mach-O objects are prefixed with an underscore.
+            listed_symbol = Some(if symbol.starts_with('_') { &symbol[1..] } else { symbol });
+        // GNU ld: `/usr/bin/ld: foo.foo.7rcbfp3g-cgu.0.rcgu.o: in function `main':`
+        } else if let Some(i) =
+            trimmed.find(": in function ").or_else(|| trimmed.find(": In function "))
+        {
+            gnu_object = trimmed[..i].rsplit(": ").next();
+        // GNU ld: `foo.7rcbfp3g-cgu.0:(.text.main+0x5): undefined reference to `foo'`
+        } else if let Some(symbol) = after(trimmed, "undefined reference to ") {
+            let location = trimmed[..trimmed.len() - symbol.len()].rsplit(": ").nth(1);
+            let referenced_by = match location.and_then(|location| location.find(":(")) {
+                Some(end) => location.map(|location| &location[..end]),
+                None => gnu_object,
+            };
+            problems
+                .push(LinkerProblem::UndefinedSymbol { symbol: unquote(symbol), referenced_by });
+        // link.exe: `foo.foo.7rcbfp3g-cgu.0.rcgu.o : error LNK2019: unresolved external
+        // symbol foo referenced in function main`
+        } else if let Some(symbol) = after(trimmed, "unresolved external symbol ") {
+            let symbol = match symbol.find(" referenced in function ") {
+                Some(end) => &symbol[..end],
+                None => symbol,
+            };
+            let referenced_by = trimmed.find(" : error LNK").map(|end| &trimmed[..end]);
+            problems.push(LinkerProblem::UndefinedSymbol { symbol, referenced_by });
+        // link.exe: `LINK : fatal error LNK1181: cannot open input file 'foo.lib'`
+        } else if let Some(file) = after(trimmed, "cannot open input file ") {
+            let file = file.trim_matches('\'');
+            if file.ends_with(".lib") {
+                let name = &file[..file.len() - ".lib".len()];
+                problems.push(LinkerProblem::MissingLibrary { name });
+            }
+        // GNU ld: `/usr/bin/ld: cannot find -lfoo`
+        // lld: `ld.lld: error: unable to find library -lfoo`
+        // ld64: `ld: library not found for -lfoo`
+        } else if let Some(name) = after(trimmed, "cannot find -l")
+            .or_else(|| after(trimmed, "unable to find library -l"))
+            .or_else(|| after(trimmed, "library not found for -l"))
+        {
+            let name = name.split(|c: char| c == ':' || c.is_whitespace()).next().unwrap();
+            if !name.is_empty() {
+                problems.push(LinkerProblem::MissingLibrary { name });
+            }
+        }
+    }
+    if let Some(symbol) = listed_symbol {
+        push_unreferenced_symbol(&mut problems, symbol);
+    }
+    problems
+}
+
+/// Records an undefined symbol of which the linker listed no references from
+/// object files it could name.
+fn push_unreferenced_symbol<'a>(problems: &mut Vec<LinkerProblem<'a>>, symbol: &'a str) {
+    let listed = problems.iter().any(|problem| match *problem {
+        LinkerProblem::UndefinedSymbol { symbol: listed, .. } => listed == symbol,
+        LinkerProblem::MissingLibrary { .. } => false,
+    });
+    if !listed {
+        problems.push(LinkerProblem::UndefinedSymbol { symbol, referenced_by: None });
+    }
+}
+
+/// Returns which of `crate_names` the object file or codegen unit was compiled
+/// from. Their names start with the name of the crate, possibly followed by its
+/// disambiguator, as in `foo.foo.7rcbfp3g-cgu.0.rcgu.o`,
+/// `foo-6d2a9a4c56e7d1b3.foo.7rcbfp3g-cgu.0.rcgu.o` and `foo.7rcbfp3g-cgu.0`.
+/// The objects of archives may be named like `libfoo.rlib(foo.o)`.
+fn crate_of_object<'a>(object: &str, crate_names: &[&'a str]) -> Option<&'a str> {
+    let file_name = object.trim_end_matches(')').rsplit(|c| c == '/' || c == '\\' || c == '(');
+    let file_name = file_name.next()?;
+    if !file_name.contains("-cgu.") {
+        return None;
+    }
+    let crate_name = file_name.split(|c| c == '.' || c == '-').next()?;
+    crate_names.iter().find(|&&name| name == crate_name).cloned()
+}
+
+pub fn report_linker_problems(sess: &Session, codegen_results: &CodegenResults, output: &str) {
+    let crate_info = &codegen_results.crate_info;
+    let local_crate_name = codegen_results.crate_name.as_str();
+    let crate_names: Vec<&str> = crate_info
+        .crate_name
+        .values()
+        .map(|name| &name[..])
+        .chain(Some(&*local_crate_name))
+        .collect();
+
+    let mut undefined_symbols = FxIndexMap::default();
+    let mut missing_libraries = FxIndexSet::default();
+    for problem in parse_linker_output(output) {
+        match problem {
+            LinkerProblem::UndefinedSymbol { symbol, referenced_by } => {
+                let crates: &mut Vec<_> = undefined_symbols.entry(symbol).or_insert_with(Vec::new);
+                let crate_name =
+                    referenced_by.and_then(|object| crate_of_object(object, &crate_names));
+                if let Some(crate_name) = crate_name {
+                    if !crates.contains(&crate_name) {
+                        crates.push(crate_name);
+                    }
+                }
+            }
+            LinkerProblem::MissingLibrary { name } => {
+                missing_libraries.insert(name);
+            }
+        }
+    }
+
+    for (symbol, crates) in undefined_symbols {
+        let demangled = match rustc_demangle::try_demangle(symbol) {
+            Ok(demangled) => format!("{:#}", demangled),
+            Err(_) => symbol.to_string(),
+        };
+        let mut err = sess.struct_err(&format!("undefined symbol `{}`", demangled));
+        if demangled != symbol {
+            err.note(&format!("the mangled name of the symbol is `{}`", symbol));
+        }
+        if !crates.is_empty() {
+            let crates: Vec<_> = crates.iter().map(|name| format!("`{}`", name)).collect();
+            err.note(&format!(
+                "referenced by the code of crate{} {}",
+                if crates.len() == 1 { "" } else { "s" },
+                crates.join(", ")
+            ));
+        }
+        err.emit();
+    }
+
+    let native_libraries = crate_info
+        .native_libraries
+        .iter()
+        .filter(|&(&cnum, _)| cnum != LOCAL_CRATE)
+        .flat_map(|(&cnum, libs)| libs.iter().map(move |lib| (cnum, lib)))
+        .chain(crate_info.used_libraries.iter().map(|lib| (LOCAL_CRATE, lib)));
+    let native_libraries: Vec<(CrateNum, &NativeLibrary)> = native_libraries.collect();
+    for name in missing_libraries {
+        let msg = format!("could not find native library `{}`", name);
+        let requested_by = native_libraries
+            .iter()
+            .find(|(_, lib)| lib.name.map_or(false, |lib_name| &*lib_name.as_str() == name));
+        let mut err = match requested_by {
+            Some(&(cnum, lib)) => {
+                let mut err = match lib.span {
+                    Some(span) => {
+                        let mut err = sess.struct_span_err(span, &msg);
+                        err.span_label(span, "library requested here");
+                        err
+                    }
+                    None => {
+                        let mut err = sess.struct_err(&msg);
+                        err.note(&format!("requested with `-l {}` on the command line", name));
+                        err
+                    }
+                };
+                if cnum != LOCAL_CRATE {
+                    let crate_name = &crate_info.crate_name[&cnum];
+                    err.note(&format!("the library is a dependency of crate `{}`", crate_name));
+                }
+                err
+            }
+            None => sess.struct_err(&msg),
+        };
+        err.help("add the directory containing the library with `-L native=<path>`");
+        err.emit();
+    }
+}
@@ -0,0 +1,103 @@
+use super::{crate_of_object, parse_linker_output, LinkerProblem};
+
+fn undefined<'a>(symbol: &'a str, referenced_by: Option<&'a str>) -> LinkerProblem<'a> {
+    LinkerProblem::UndefinedSymbol { symbol, referenced_by }
+}
+
+#[test]
+fn test_gnu_ld() {
+    let output = "\
+/usr/bin/ld: foo.foo.7rcbfp3g-cgu.0.rcgu.o: in function `foo::main':
+foo.7rcbfp3g-cgu.0:(.text._ZN3foo4main17h0123456789abcdefE+0x5): undefined reference to `bar'
+/usr/bin/ld: /tmp/libbaz.rlib(baz.o): in function `baz':
+(.text.baz+0x1): undefined reference to `qux'
+/usr/bin/ld: cannot find -lmissing
+collect2: error: ld returned 1 exit status";
+    assert_eq!(
+        parse_linker_output(output),
+        vec![
+            undefined("bar", Some("foo.7rcbfp3g-cgu.0")),
+            undefined("qux", Some("/tmp/libbaz.rlib(baz.o)")),
+            LinkerProblem::MissingLibrary { name: "missing" },
+        ]
+    );
+}
+
+#[test]
+fn test_lld() {
+    let output = "\
+ld.lld: error: undefined symbol: bar
+>>> referenced by foo.7rcbfp3g-cgu.0
+>>>               foo.foo.7rcbfp3g-cgu.0.rcgu.o:(foo::main::h0123456789abcdef)
+>>> referenced by foo.7rcbfp3g-cgu.1
+>>>               foo.foo.7rcbfp3g-cgu.1.rcgu.o:(foo::other::h0123456789abcdef)
+ld.lld: error: undefined symbol: qux
+ld.lld: error: unable to find library -lmissing";
+    assert_eq!(
+        parse_linker_output(output),
+        vec![
+            undefined("bar", Some("foo.foo.7rcbfp3g-cgu.0.rcgu.o")),
+            undefined("bar", Some("foo.foo.7rcbfp3g-cgu.1.rcgu.o")),
+            undefined("qux", None),
+            LinkerProblem::MissingLibrary { name: "missing" },
+        ]
+    );
+}
+
+#[test]
+fn test_ld64() {
+    let output = "\
+Undefined symbols for architecture x86_64:
+  \"_bar\", referenced from:
+      foo::main::h0123456789abcdef in foo.foo.7rcbfp3g-cgu.0.rcgu.o
+  \"__ZN3baz3qux17h0123456789abcdefE\", referenced from:
+ld: symbol(s) not found for architecture x86_64
+ld: library not found for -lmissing";
+    assert_eq!(
+        parse_linker_output(output),
+        vec![
+            undefined("bar", Some("foo.foo.7rcbfp3g-cgu.0.rcgu.o")),
+            undefined("_ZN3baz3qux17h0123456789abcdefE", None),
+            LinkerProblem::MissingLibrary { name: "missing" },
+        ]
+    );
+}
+
+#[test]
+fn test_msvc() {
+    let output = "\
+foo.foo.7rcbfp3g-cgu.0.rcgu.o : error LNK2019: unresolved external symbol bar referenced in \
+function _ZN3foo4main17h0123456789abcdefE
+foo.exe : fatal error LNK1120: 1 unresolved externals
+LINK : fatal error LNK1181: cannot open input file 'missing.lib'";
+    assert_eq!(
+        parse_linker_output(output),
+        vec![
+            undefined("bar", Some("foo.foo.7rcbfp3g-cgu.0.rcgu.o")),
+            LinkerProblem::MissingLibrary { name: "missing" },
+        ]
+    );
+}
+
+#[test]
+fn test_crate_of_object() {
+    let crates = ["foo", "bar_baz"];
+    assert_eq!(crate_of_object("foo.foo.7rcbfp3g-cgu.0.rcgu.o", &crates), Some("foo"));
+    assert_eq!(crate_of_object("foo.7rcbfp3g-cgu.0", &crates), Some("foo"));
+    assert_eq!(
+        crate_of_object(
+            "/tmp/rustcXYZ/bar_baz-6d2a9a4c56e7d1b3.bar_baz.3a1fbbbh-cgu.0.rcgu.o",
+            &crates
+        ),
+        Some("bar_baz")
+    );
+    assert_eq!(
+        crate_of_object(
+            "libbar_baz-6d2a9a4c56e7d1b3.rlib(bar_baz-6d2a9a4c56e7d1b3.bar_baz.3a1fbbbh-cgu.0.rcgu.o)",
+            &crates
+        ),
+        Some("bar_baz")
+    );
+    assert_eq!(crate_of_object("/tmp/libqux.a(qux.o)", &crates), None);
+    assert_eq!(crate_of_object("other.other.7rcbfp3g-cgu.0.rcgu.o", &crates), None);
+}
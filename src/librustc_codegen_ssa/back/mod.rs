@@ -2,7 +2,9 @@ pub mod archive;
 pub mod command;
 pub mod link;
 pub mod linker;
+pub mod linker_diagnostics;
 pub mod lto;
 pub mod rpath;
+pub mod stack_usage;
 pub mod symbol_export;
 pub mod write;
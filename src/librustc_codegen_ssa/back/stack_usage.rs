@@ -0,0 +1,166 @@
+//! The stack usage analysis of `-Z print-stack-usage` and `-Z stack-limit-warn`.
+//!
+//! The size of the stack frame of every function is read back from the
+//! `.stack_sizes` sections of the object files, which LLVM emits for ELF
+//! targets. The worst-case stack usage of a function is the size of its own
+//! frame plus the largest worst-case stack usage among the functions it
+//! references, according to the call graph of the collected mono items. The
+//! call graph also contains references that are not calls, so the result is an
+//! upper bound. Calls into other crates are not accounted for, and functions
+//! that are part of a recursive call chain have no bound at all.
+
+use crate::traits::*;
+use crate::{CompiledModule, CrateInfo};
+
+use rustc::session::Session;
+use rustc::util::nodemap::FxHashMap;
+use syntax_pos::symbol::Symbol;
+
+#[derive(Copy, Clone)]
+enum StackUsage {
+    /// The worst-case stack usage in bytes, and the callee through which the
+    /// deepest call chain continues, if any.
+    Bounded(u64, Option<Symbol>),
+    /// The function can (indirectly) call itself.
+    Recursive,
+}
+
+struct StackUsageAnalysis<'a> {
+    call_graph: &'a FxHashMap<Symbol, Vec<Symbol>>,
+    frame_sizes: FxHashMap<Symbol, u64>,
+    /// `None` while the stack usage of the function is being computed, which
+    /// is how recursion is detected.
+    stack_usage: FxHashMap<Symbol, Option<StackUsage>>,
+}
+
+impl StackUsageAnalysis<'_> {
+    fn stack_usage(&mut self, function: Symbol) -> StackUsage {
+        match self.stack_usage.get(&function) {
+            Some(Some(usage)) => return *usage,
+            Some(None) => return StackUsage::Recursive,
+            None => {}
+        }
+        self.stack_usage.insert(function, None);
+
+        // Functions without a frame size of their own were inlined into their
+        // callers, or not emitted at all, but the functions they call still
+        // contribute to the stack usage of their callers.
+        let frame_size = self.frame_sizes.get(&function).cloned().unwrap_or(0);
+        let mut usage = StackUsage::Bounded(frame_size, None);
+        let callees = self.call_graph.get(&function).map_or(&[][..], |callees| &callees[..]);
+        for &callee in callees {
+            match (self.stack_usage(callee), usage) {
+                (StackUsage::Recursive, _) => usage = StackUsage::Recursive,
+                (StackUsage::Bounded(callee_usage, _), StackUsage::Bounded(max, _))
+                    if frame_size + callee_usage > max =>
+                {
+                    usage = StackUsage::Bounded(frame_size + callee_usage, Some(callee));
+                }
+                _ => {}
+            }
+        }
+
+        self.stack_usage.insert(function, Some(usage));
+        usage
+    }
+
+    /// The deepest call chain starting at `function`, which must have a
+    /// bounded stack usage.
+    fn deepest_call_chain(&self, function: Symbol) -> Vec<Symbol> {
+        let mut chain = vec![function];
+        let mut next = function;
+        while let Some(Some(StackUsage::Bounded(_, Some(callee)))) = self.stack_usage.get(&next) {
+            chain.push(*callee);
+            next = *callee;
+        }
+        chain
+    }
+}
+
+fn demangle(symbol: Symbol) -> String {
+    format!("{:#}", rustc_demangle::demangle(&symbol.as_str()))
+}
+
+pub fn report_stack_usage<B: WriteBackendMethods>(
+    sess: &Session,
+    crate_info: &CrateInfo,
+    modules: &[&CompiledModule],
+) {
+    let call_graph = match crate_info.stack_usage_call_graph {
+        Some(ref call_graph) => call_graph,
+        None => return,
+    };
+
+    let mut frame_sizes = FxHashMap::default();
+    for object in modules.iter().filter_map(|module| module.object.as_ref()) {
+        match B::read_stack_sizes(sess, object) {
+            Some(stack_sizes) => {
+                for (symbol, size) in stack_sizes {
+                    frame_sizes.insert(Symbol::intern(&symbol), size);
+                }
+            }
+            None => sess.warn(&format!("could not read stack sizes from `{}`", object.display())),
+        }
+    }
+
+    if frame_sizes.is_empty() {
+        sess.warn("no stack sizes were found; the stack usage analysis requires an ELF target");
+        return;
+    }
+
+    let mut analysis =
+        StackUsageAnalysis { call_graph, frame_sizes, stack_usage: FxHashMap::default() };
+
+    let mut functions: Vec<_> = analysis
+        .frame_sizes
+        .iter()
+        .map(|(&symbol, &size)| (demangle(symbol), symbol, size))
+        .collect();
+    functions.sort();
+
+    for (name, symbol, frame_size) in functions {
+        let usage = analysis.stack_usage(symbol);
+
+        if sess.opts.debugging_opts.print_stack_usage {
+            match usage {
+                StackUsage::Bounded(total, _) => println!(
+                    "STACK_USAGE {} frame {} bytes, worst case {} bytes",
+                    name, frame_size, total
+                ),
+                StackUsage::Recursive => println!(
+                    "STACK_USAGE {} frame {} bytes, worst case unbounded (recursive)",
+                    name, frame_size
+                ),
+            }
+        }
+
+        if let Some(limit) = sess.opts.debugging_opts.stack_limit_warn {
+            let limit = limit as u64;
+            match usage {
+                StackUsage::Bounded(total, _) if total > limit => {
+                    let chain: Vec<_> =
+                        analysis.deepest_call_chain(symbol).into_iter().map(demangle).collect();
+                    sess.struct_warn(&format!(
+                        "function `{}` uses up to {} bytes of stack, \
+                         exceeding the limit of {} bytes",
+                        name, total, limit
+                    ))
+                    .note(&format!("the deepest call chain is `{}`", chain.join("` -> `")))
+                    .emit();
+                }
+                StackUsage::Recursive if frame_size > limit => {
+                    sess.struct_warn(&format!(
+                        "function `{}` uses {} bytes of stack, exceeding the limit of {} bytes",
+                        name, frame_size, limit
+                    ))
+                    .note(
+                        "the function is part of a recursive call chain, \
+                         so its stack usage is unbounded",
+                    )
+                    .emit();
+                }
+                _ => {}
+            }
+        }
+    }
+}
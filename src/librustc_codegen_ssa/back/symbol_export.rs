@@ -225,7 +225,7 @@ fn exported_symbols_provider_local(
         let need_visibility = tcx.sess.target.target.options.dynamic_linking
             && !tcx.sess.target.target.options.only_cdylib;
 
-        let (_, cgus) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+        let (_, cgus, _) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
 
         for (mono_item, &(linkage, visibility)) in cgus.iter().flat_map(|cgu| cgu.items().iter()) {
             if linkage != Linkage::External {
@@ -2,6 +2,7 @@ use super::command::Command;
 use super::link::{self, get_linker, remove};
 use super::linker::LinkerInfo;
 use super::lto::{self, SerializedModule};
+use super::stack_usage;
 use super::symbol_export::ExportedSymbols;
 use crate::{
     CachedModuleCodegen, CodegenResults, CompiledModule, CrateInfo, ModuleCodegen, ModuleKind,
@@ -14,7 +15,7 @@ use rustc::dep_graph::{WorkProduct, WorkProductFileKind, WorkProductId};
 use rustc::hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc::middle::cstore::EncodedMetadata;
 use rustc::session::config::{
-    self, Lto, OutputFilenames, OutputType, Passes, Sanitizer, SwitchWithOptPath,
+    self, Lto, OutputFilenames, OutputType, Passes, Sanitizer, SplitDebuginfo, SwitchWithOptPath,
 };
 use rustc::session::Session;
 use rustc::ty::TyCtxt;
@@ -76,6 +77,8 @@ pub struct ModuleConfig {
     pub emit_ir: bool,
     pub emit_asm: bool,
     pub emit_obj: bool,
+    /// Whether the debuginfo of the object goes into a split DWARF object.
+    pub emit_dwarf_obj: bool,
     // Miscellaneous flags.  These are mostly copied from command-line
     // options.
     pub verify_llvm_ir: bool,
@@ -117,6 +120,7 @@ impl ModuleConfig {
             emit_ir: false,
             emit_asm: false,
             emit_obj: false,
+            emit_dwarf_obj: false,
             obj_is_bitcode: false,
             embed_bitcode: false,
             embed_bitcode_marker: false,
@@ -362,6 +366,11 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
         modules_config.passes.push("insert-gcov-profiling".to_owned())
     }
 
+    if sess.opts.debugging_opts.instrument_coverage {
+        // Lowers the counter increments of the coverage instrumentation.
+        modules_config.passes.push("instrprof".to_owned())
+    }
+
     modules_config.pgo_gen = sess.opts.cg.profile_generate.clone();
     modules_config.pgo_use = sess.opts.cg.profile_use.clone();
     modules_config.sanitizer = sess.opts.debugging_opts.sanitizer.clone();
@@ -425,9 +434,14 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
             }
             OutputType::Mir => {}
             OutputType::DepInfo => {}
+            OutputType::CHeader => {}
         }
     }
 
+    // Only the regular modules have debuginfo to split out.
+    modules_config.emit_dwarf_obj =
+        modules_config.emit_obj && sess.split_debuginfo() != SplitDebuginfo::Off;
+
     modules_config.set_flags(sess, no_builtins);
     metadata_config.set_flags(sess, no_builtins);
     allocator_config.set_flags(sess, no_builtins);
@@ -494,6 +508,9 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
         if let Some(ref path) = module.bytecode_compressed {
             files.push((WorkProductFileKind::BytecodeCompressed, path.clone()));
         }
+        if let Some(ref path) = module.dwarf_object {
+            files.push((WorkProductFileKind::DwarfObject, path.clone()));
+        }
 
         if let Some((id, product)) =
             copy_cgu_workproducts_to_incr_comp_cache_dir(sess, &module.name, &files)
@@ -586,7 +603,11 @@ fn produce_final_output_artifacts(
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::CHeader => {}
         }
     }
 
@@ -825,6 +846,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     let mut object = None;
     let mut bytecode = None;
     let mut bytecode_compressed = None;
+    let mut dwarf_object = None;
     for (kind, saved_file) in &module.source.saved_files {
         let obj_out = match kind {
             WorkProductFileKind::Object => {
@@ -845,6 +867,11 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
                 bytecode_compressed = Some(path.clone());
                 path
             }
+            WorkProductFileKind::DwarfObject => {
+                let path = cgcx.output_filenames.temp_path_dwo(Some(&module.name));
+                dwarf_object = Some(path.clone());
+                path
+            }
         };
         let source_file = in_incr_comp_dir(&incr_comp_session_dir, &saved_file);
         debug!(
@@ -867,6 +894,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     assert_eq!(object.is_some(), module_config.emit_obj);
     assert_eq!(bytecode.is_some(), module_config.emit_bc);
     assert_eq!(bytecode_compressed.is_some(), module_config.emit_bc_compressed);
+    assert_eq!(dwarf_object.is_some(), module_config.emit_dwarf_obj);
 
     Ok(WorkItemResult::Compiled(CompiledModule {
         name: module.name,
@@ -874,6 +902,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
         object,
         bytecode,
         bytecode_compressed,
+        dwarf_object,
     }))
 }
 
@@ -1746,6 +1775,12 @@ impl<B: ExtraBackendMethods> OngoingCodegen<B> {
 
         let work_products =
             copy_all_cgu_workproducts_to_incr_comp_cache_dir(sess, &compiled_modules);
+
+        // The object files may be removed in `produce_final_output_artifacts`.
+        let modules: Vec<_> =
+            compiled_modules.modules.iter().chain(&compiled_modules.allocator_module).collect();
+        stack_usage::report_stack_usage::<B>(sess, &self.crate_info, &modules);
+
         produce_final_output_artifacts(sess, &compiled_modules, &self.output_filenames);
 
         // FIXME: time_llvm_passes support - does this use a global context or
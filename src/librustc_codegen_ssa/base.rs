@@ -738,6 +738,7 @@ impl CrateInfo {
             lang_item_to_crate: Default::default(),
             missing_lang_items: Default::default(),
             dependency_formats: tcx.dependency_formats(LOCAL_CRATE),
+            stack_usage_call_graph: tcx.collect_and_partition_mono_items(LOCAL_CRATE).2,
         };
         let lang_items = tcx.lang_items();
 
@@ -809,7 +810,7 @@ pub fn provide_both(providers: &mut Providers<'_>) {
             config::OptLevel::SizeMin => config::OptLevel::Default,
         };
 
-        let (defids, _) = tcx.collect_and_partition_mono_items(cratenum);
+        let (defids, _, _) = tcx.collect_and_partition_mono_items(cratenum);
         for id in &*defids {
             let hir::CodegenFnAttrs { optimize, .. } = tcx.codegen_fn_attrs(*id);
             match optimize {
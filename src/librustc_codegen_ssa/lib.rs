@@ -35,6 +35,7 @@ use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::svh::Svh;
 use rustc_data_structures::sync::Lrc;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use syntax_pos::symbol::Symbol;
 
 pub mod back;
@@ -69,6 +70,7 @@ impl<M> ModuleCodegen<M> {
         emit_obj: bool,
         emit_bc: bool,
         emit_bc_compressed: bool,
+        emit_dwarf_obj: bool,
         outputs: &OutputFilenames,
     ) -> CompiledModule {
         let object = emit_obj.then(|| outputs.temp_path(OutputType::Object, Some(&self.name)));
@@ -78,6 +80,7 @@ impl<M> ModuleCodegen<M> {
                 .temp_path(OutputType::Bitcode, Some(&self.name))
                 .with_extension(RLIB_BYTECODE_EXTENSION)
         });
+        let dwarf_object = emit_dwarf_obj.then(|| outputs.temp_path_dwo(Some(&self.name)));
 
         CompiledModule {
             name: self.name.clone(),
@@ -85,6 +88,7 @@ impl<M> ModuleCodegen<M> {
             object,
             bytecode,
             bytecode_compressed,
+            dwarf_object,
         }
     }
 }
@@ -96,6 +100,9 @@ pub struct CompiledModule {
     pub object: Option<PathBuf>,
     pub bytecode: Option<PathBuf>,
     pub bytecode_compressed: Option<PathBuf>,
+    /// The split DWARF object written along with `object`, which is never
+    /// linked, but which the debuginfo of the linked output refers to.
+    pub dwarf_object: Option<PathBuf>,
 }
 
 pub struct CachedModuleCodegen {
@@ -136,6 +143,9 @@ pub struct CrateInfo {
     pub lang_item_to_crate: FxHashMap<LangItem, CrateNum>,
     pub missing_lang_items: FxHashMap<CrateNum, Vec<LangItem>>,
     pub dependency_formats: Lrc<Dependencies>,
+    /// The call graph used for the stack usage analysis, if it was requested
+    /// with `-Z print-stack-usage` or `-Z stack-limit-warn`.
+    pub stack_usage_call_graph: Option<Arc<FxHashMap<Symbol, Vec<Symbol>>>>,
 }
 
 pub struct CodegenResults {
@@ -1,6 +1,7 @@
 use crate::traits::*;
 
-use rustc::ty::{self, Instance, Ty};
+use rustc::ty::{self, Instance, Ty, TyCtxt};
+use rustc_codegen_utils::cfi;
 use rustc_target::abi::call::FnAbi;
 
 #[derive(Copy, Clone, Debug)]
@@ -34,6 +35,27 @@ impl<'a, 'tcx> VirtualIndex {
         ptr
     }
 
+    /// Returns the address of this entry in `llvtable`, which is what the
+    /// control flow integrity checks of calls through the vtable test.
+    pub fn get_addr<Bx: BuilderMethods<'a, 'tcx>>(
+        self,
+        bx: &mut Bx,
+        llvtable: Bx::Value,
+    ) -> Bx::Value {
+        let llvtable = bx.pointercast(llvtable, bx.type_ptr_to(bx.type_i8p()));
+        bx.inbounds_gep(llvtable, &[bx.const_usize(self.0)])
+    }
+
+    /// The type identifier of this entry in vtables for `object_ty`, which
+    /// must be a trait object type.
+    pub fn cfi_typeid(self, tcx: TyCtxt<'tcx>, object_ty: Ty<'tcx>) -> String {
+        let trait_ref = match object_ty.kind {
+            ty::Dynamic(ref predicates, _) => predicates.principal(),
+            _ => bug!("cfi_typeid: {:?} is not a trait object", object_ty),
+        };
+        cfi::vtable_entry_typeid(tcx, trait_ref, self.0)
+    }
+
     pub fn get_usize<Bx: BuilderMethods<'a, 'tcx>>(
         self,
         bx: &mut Bx,
@@ -118,6 +140,16 @@ pub fn get_vtable<'tcx, Cx: CodegenMethods<'tcx>>(
     let align = cx.data_layout().pointer_align.abi;
     let vtable = cx.static_addr_of(vtable_const, align, Some("vtable"));
 
+    // Mark the entries holding functions as valid targets of calls through
+    // vtables of this trait.
+    if tcx.sess.emits_cfi_type_metadata() {
+        let ptr_size = tcx.data_layout.pointer_size.bytes();
+        for index in (0..components.len() as u64).filter(|&i| i != SIZE.0 && i != ALIGN.0) {
+            let typeid = cfi::vtable_entry_typeid(tcx, trait_ref, index);
+            cx.add_type_metadata(vtable, index * ptr_size, &typeid);
+        }
+    }
+
     cx.create_vtable_metadata(ty, vtable);
 
     cx.vtables().borrow_mut().insert((ty, trait_ref), vtable);
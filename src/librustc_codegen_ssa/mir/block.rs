@@ -5,8 +5,10 @@ use crate::MemFlags;
 use rustc::middle::lang_items;
 use rustc::mir::interpret::PanicInfo;
 use rustc::mir::{self, PlaceBase, Static, StaticKind};
+use rustc::session::config::Sanitizer;
 use rustc::ty::layout::{self, FnAbiExt, HasTyCtxt, LayoutOf};
 use rustc::ty::{self, Instance, Ty, TypeFoldable};
+use rustc_codegen_utils::cfi;
 use rustc_index::vec::Idx;
 use rustc_target::abi::call::{ArgAbi, FnAbi, PassMode};
 use rustc_target::spec::abi::Abi;
@@ -340,6 +342,11 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 let fn_abi = FnAbi::of_instance(&bx, virtual_drop, &[]);
                 let vtable = args[1];
                 args = &args[..1];
+                if bx.sess().opts.debugging_opts.sanitizer == Some(Sanitizer::Cfi) {
+                    let entry = meth::DESTRUCTOR.get_addr(&mut bx, vtable);
+                    let typeid = meth::DESTRUCTOR.cfi_typeid(bx.tcx(), ty);
+                    bx = self.codegen_cfi_check(bx, entry, &typeid, helper.terminator.source_info);
+                }
                 (meth::DESTRUCTOR.get_fn(&mut bx, vtable, &fn_abi), fn_abi)
             }
             _ => (bx.get_fn_addr(drop_fn), FnAbi::of_instance(&bx, drop_fn, &[])),
@@ -562,6 +569,37 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             return;
         }
 
+        // Coverage counters are only emitted when instrumenting this crate;
+        // they can also come from the MIR of other crates.
+        if intrinsic == Some("count_code_region") {
+            if bx.tcx().sess.opts.debugging_opts.instrument_coverage {
+                let tcx = bx.tcx();
+                let arg = |i: usize| {
+                    match args[i] {
+                        mir::Operand::Constant(ref constant) => constant.literal.try_eval_bits(
+                            tcx,
+                            ty::ParamEnv::reveal_all(),
+                            constant.literal.ty,
+                        ),
+                        _ => None,
+                    }
+                    .unwrap_or_else(|| span_bug!(span, "non-constant `count_code_region` argument"))
+                };
+                let (function_source_hash, num_counters, index) = (arg(0), arg(1), arg(2));
+                bx.add_counter_region(
+                    self.instance,
+                    function_source_hash as u64,
+                    num_counters as u32,
+                    index as u32,
+                    span,
+                );
+            }
+            let target = destination.as_ref().unwrap().1;
+            helper.maybe_sideeffect(self.mir, &mut bx, &[target]);
+            helper.funclet_br(self, &mut bx, target);
+            return;
+        }
+
         // The arguments we'll be passing. Plus one to account for outptr, if used.
         let arg_count = fn_abi.args.len() + fn_abi.ret.is_indirect() as usize;
         let mut llargs = Vec::with_capacity(arg_count);
@@ -694,6 +732,9 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             (&args[..], None)
         };
 
+        // The vtable of the receiver of virtual calls.
+        let mut llvtable = None;
+
         'make_args: for (i, arg) in first_args.iter().enumerate() {
             let mut op = self.codegen_operand(&mut bx, arg);
 
@@ -730,6 +771,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                             llfn = Some(
                                 meth::VirtualIndex::from_index(idx).get_fn(&mut bx, meta, &fn_abi),
                             );
+                            llvtable = Some(meta);
                             llargs.push(data_ptr);
                             continue 'make_args;
                         }
@@ -738,6 +780,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 } else if let Ref(data_ptr, Some(meta), _) = op.val {
                     // by-value dynamic dispatch
                     llfn = Some(meth::VirtualIndex::from_index(idx).get_fn(&mut bx, meta, &fn_abi));
+                    llvtable = Some(meta);
                     llargs.push(data_ptr);
                     continue;
                 } else {
@@ -787,6 +830,26 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             _ => span_bug!(span, "no llfn for call"),
         };
 
+        // Check that indirect calls go to functions of the type the caller
+        // expects, which is the case for the entries of vtables of the trait
+        // of the receiver, and for functions of the signature of the function
+        // pointer.
+        if bx.sess().opts.debugging_opts.sanitizer == Some(Sanitizer::Cfi) {
+            let target = match (def, llvtable) {
+                (Some(ty::InstanceDef::Virtual(_, idx)), Some(llvtable)) => {
+                    let entry = meth::VirtualIndex::from_index(idx);
+                    let object_ty = instance.unwrap().substs.type_at(0);
+                    let typeid = entry.cfi_typeid(bx.tcx(), object_ty);
+                    Some((entry.get_addr(&mut bx, llvtable), typeid))
+                }
+                (None, _) => Some((fn_ptr, cfi::fn_ptr_typeid(bx.tcx(), sig))),
+                _ => None,
+            };
+            if let Some((pointer, typeid)) = target {
+                bx = self.codegen_cfi_check(bx, pointer, &typeid, terminator.source_info);
+            }
+        }
+
         if let Some((_, target)) = destination.as_ref() {
             helper.maybe_sideeffect(self.mir, &mut bx, &[*target]);
         }
@@ -803,6 +866,29 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
 }
 
 impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
+    /// Emits a check that `pointer` is a member of the type `typeid`, which
+    /// traps if it isn't, and returns the builder of the block following the
+    /// check.
+    fn codegen_cfi_check(
+        &mut self,
+        mut bx: Bx,
+        pointer: Bx::Value,
+        typeid: &str,
+        source_info: mir::SourceInfo,
+    ) -> Bx {
+        let is_member = bx.type_test(pointer, typeid);
+        let mut ok_bx = bx.build_sibling_block("cfi_ok");
+        let mut violation_bx = bx.build_sibling_block("cfi_violation");
+        bx.cond_br(is_member, ok_bx.llbb(), violation_bx.llbb());
+
+        self.set_debug_loc(&mut violation_bx, source_info);
+        violation_bx.abort();
+        violation_bx.unreachable();
+
+        self.set_debug_loc(&mut ok_bx, source_info);
+        ok_bx
+    }
+
     pub fn codegen_block(&mut self, bb: mir::BasicBlock) {
         let mut bx = self.build_block(bb);
         let mir = self.mir;
@@ -0,0 +1,22 @@
+use super::BackendTypes;
+use rustc::ty::Instance;
+use syntax_pos::Span;
+
+pub trait CoverageInfoMethods: BackendTypes {
+    /// Emits the coverage map of the functions that got counters into the
+    /// module.
+    fn coverageinfo_finalize(&self);
+}
+
+pub trait CoverageInfoBuilderMethods<'tcx>: BackendTypes {
+    /// Increments the counter `index` of the `num_counters` counters of
+    /// `instance`, and records `region` as the code that counter covers.
+    fn add_counter_region(
+        &mut self,
+        instance: Instance<'tcx>,
+        function_source_hash: u64,
+        num_counters: u32,
+        index: u32,
+        region: Span,
+    );
+}
@@ -21,6 +21,9 @@ pub trait IntrinsicCallMethods<'tcx>: BackendTypes {
     fn assume(&mut self, val: Self::Value);
     fn expect(&mut self, cond: Self::Value, expected: bool) -> Self::Value;
     fn sideeffect(&mut self);
+    /// Checks whether `pointer` was marked as a member of the type `typeid`
+    /// with `MiscMethods::add_type_metadata`, for `-Z sanitizer=cfi`.
+    fn type_test(&mut self, pointer: Self::Value, typeid: &str) -> Self::Value;
     /// Trait method used to inject `va_start` on the "spoofed" `VaListImpl` in
     /// Rust defined C-variadic functions.
     fn va_start(&mut self, val: Self::Value) -> Self::Value;
@@ -21,4 +21,7 @@ pub trait MiscMethods<'tcx>: BackendTypes {
     fn set_frame_pointer_elimination(&self, llfn: Self::Function);
     fn apply_target_cpu_attr(&self, llfn: Self::Function);
     fn create_used_variable(&self);
+    /// Marks `offset` bytes into the function or static `global` as a member of
+    /// the type `typeid`, for `-Z sanitizer=cfi`.
+    fn add_type_metadata(&self, global: Self::Value, offset: u64, typeid: &str);
 }
@@ -3,8 +3,11 @@ use crate::back::write::{CodegenContext, FatLTOInput, ModuleConfig};
 use crate::{CompiledModule, ModuleCodegen};
 
 use rustc::dep_graph::WorkProduct;
+use rustc::session::Session;
 use rustc_errors::{FatalError, Handler};
 
+use std::path::Path;
+
 pub trait WriteBackendMethods: 'static + Sized + Clone {
     type Module: Send + Sync;
     type TargetMachine;
@@ -53,6 +56,9 @@ pub trait WriteBackendMethods: 'static + Sized + Clone {
         config: &ModuleConfig,
         thin: bool,
     );
+    /// Returns the symbol name and stack frame size of every function in the
+    /// object file at `object`, or `None` if they could not be read.
+    fn read_stack_sizes(sess: &Session, object: &Path) -> Option<Vec<(String, u64)>>;
 }
 
 pub trait ThinBufferMethods: Send + Sync {
@@ -0,0 +1,536 @@
+//! Generation of C headers for `--emit=c-header`.
+//!
+//! The header declares every `#[no_mangle]` (or `#[export_name]`) function
+//! or inherent method with the C ABI that is defined in the crate, along with
+//! the types that appear in their signatures. Structs and unions have to be
+//! `#[repr(C)]`, enums have to be fieldless and either `#[repr(C)]` or
+//! `#[repr(<integer>)]`, and `#[repr(transparent)]` types are declared as the
+//! type they wrap. Arrays can only be used behind pointers and as fields, as C
+//! functions can't take or return them by value. Zero-sized fields are left
+//! out, which is only allowed if they don't affect the layout. Any other type
+//! that has no C equivalent, and any name that is a keyword or a reserved
+//! identifier in C or C++, is reported as an error.
+
+use rustc::hir::def::DefKind;
+use rustc::hir::def_id::{DefId, LOCAL_CRATE};
+use rustc::hir::{self, CodegenFnAttrFlags};
+use rustc::session::config::{OutputFilenames, OutputType};
+use rustc::ty::subst::SubstsRef;
+use rustc::ty::{self, AdtDef, AdtKind, ParamEnv, Ty, TyCtxt};
+use rustc::util::nodemap::FxHashMap;
+use rustc_target::spec::abi::Abi;
+use syntax::ast;
+use syntax::attr::IntType;
+use syntax::symbol::kw;
+use syntax_pos::DUMMY_SP;
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, Write};
+
+pub fn emit_c_header(tcx: TyCtxt<'_>, outputs: &OutputFilenames) -> io::Result<()> {
+    let mut builder = HeaderBuilder {
+        tcx,
+        forward_decls: Vec::new(),
+        definitions: Vec::new(),
+        names: Default::default(),
+        types: Default::default(),
+    };
+
+    let krate = tcx.hir().krate();
+    let fns = krate.items.values().map(|item| item.hir_id);
+    let methods = krate.impl_items.values().map(|impl_item| impl_item.hir_id);
+
+    let mut prototypes = Vec::new();
+    for hir_id in fns.chain(methods) {
+        let def_id = tcx.hir().local_def_id(hir_id);
+        match tcx.def_kind(def_id) {
+            Some(DefKind::Fn) | Some(DefKind::Method) => {}
+            _ => continue,
+        }
+        let attrs = tcx.codegen_fn_attrs(def_id);
+        let exported =
+            attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE) || attrs.export_name.is_some();
+        if !exported || tcx.generics_of(def_id).requires_monomorphization(tcx) {
+            continue;
+        }
+        if tcx.fn_sig(def_id).abi() != Abi::C {
+            continue;
+        }
+        match builder.prototype(def_id) {
+            Ok(prototype) => prototypes.push(prototype),
+            Err(reason) => {
+                let msg =
+                    format!("`{}` cannot be declared in a C header", tcx.def_path_str(def_id));
+                tcx.sess.struct_span_err(tcx.def_span(def_id), &msg).note(&reason).emit();
+            }
+        }
+    }
+
+    if tcx.sess.has_errors() {
+        return Ok(());
+    }
+
+    let guard: String = tcx
+        .crate_name(LOCAL_CRATE)
+        .as_str()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .chain("_H".chars())
+        .collect();
+
+    let mut out = File::create(outputs.path(OutputType::CHeader))?;
+    writeln!(out, "/* Generated by rustc from the `{}` crate. */", tcx.crate_name(LOCAL_CRATE))?;
+    writeln!(out)?;
+    writeln!(out, "#ifndef {}", guard)?;
+    writeln!(out, "#define {}", guard)?;
+    writeln!(out)?;
+    writeln!(out, "#include <stdbool.h>")?;
+    writeln!(out, "#include <stdint.h>")?;
+    writeln!(out)?;
+    writeln!(out, "#ifdef __cplusplus")?;
+    writeln!(out, "extern \"C\" {{")?;
+    writeln!(out, "#endif")?;
+    for section in &[&builder.forward_decls, &builder.definitions, &prototypes] {
+        if !section.is_empty() {
+            writeln!(out)?;
+            for decl in section.iter() {
+                writeln!(out, "{}", decl)?;
+            }
+        }
+    }
+    writeln!(out)?;
+    writeln!(out, "#ifdef __cplusplus")?;
+    writeln!(out, "}}")?;
+    writeln!(out, "#endif")?;
+    writeln!(out)?;
+    writeln!(out, "#endif /* {} */", guard)?;
+    Ok(())
+}
+
+struct HeaderBuilder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// `typedef`s of all structs and unions, so that they can be referred to
+    /// through pointers before they are defined.
+    forward_decls: Vec<String>,
+    /// The type definitions, ordered such that every type is defined before it
+    /// is used by value.
+    definitions: Vec<String>,
+    /// The C names of the types that have been defined (or are being defined).
+    names: FxHashMap<Ty<'tcx>, String>,
+    /// The types that have been given each C name.
+    types: FxHashMap<String, Ty<'tcx>>,
+}
+
+impl HeaderBuilder<'tcx> {
+    fn prototype(&mut self, def_id: DefId) -> Result<String, String> {
+        let tcx = self.tcx;
+        let sig = tcx.fn_sig(def_id);
+        let sig = tcx.normalize_erasing_late_bound_regions(ParamEnv::reveal_all(), &sig);
+        let name = tcx.symbol_name(ty::Instance::mono(tcx, def_id)).name;
+        check_c_identifier(&name.as_str(), "function")?;
+        let hir_id = tcx.hir().as_local_hir_id(def_id).unwrap();
+        let body = tcx.hir().body(tcx.hir().body_owned_by(hir_id));
+
+        let mut params = Vec::new();
+        for (i, (&input, param)) in sig.inputs().iter().zip(&body.params).enumerate() {
+            let arg_name = match param.pat.kind {
+                hir::PatKind::Binding(_, _, ident, _) if ident.name != kw::Underscore => {
+                    ident.to_string()
+                }
+                _ => format!("arg{}", i),
+            };
+            check_c_identifier(&arg_name, "parameter")?;
+            params.push(self.declare_param(input, &arg_name)?);
+        }
+        let params = parameter_list(params, sig.c_variadic);
+
+        let declarator = format!("{}({})", name, params);
+        Ok(format!("{};", self.declare_return(sig.output(), &declarator)?))
+    }
+
+    /// Declares a parameter of a function, which can't be an array.
+    fn declare_param(&mut self, ty: Ty<'tcx>, declarator: &str) -> Result<String, String> {
+        self.check_not_array(ty)?;
+        self.declare(ty, declarator, false)
+    }
+
+    /// Declares `declarator` as a function returning `ty`, where `()` and `!`
+    /// are returned as `void`.
+    fn declare_return(&mut self, ty: Ty<'tcx>, declarator: &str) -> Result<String, String> {
+        if ty.is_unit() || ty.is_never() {
+            return Ok(format!("void {}", declarator));
+        }
+        self.check_not_array(ty)?;
+        self.declare(ty, declarator, false)
+    }
+
+    /// C functions can't take or return arrays by value: array parameters are
+    /// adjusted to pointers, and array return types are not allowed at all.
+    fn check_not_array(&self, mut ty: Ty<'tcx>) -> Result<(), String> {
+        loop {
+            match ty.kind {
+                ty::Array(..) => {
+                    return Err(format!(
+                        "`{}` is an array, which C functions can't take or return by value",
+                        ty
+                    ));
+                }
+                ty::Adt(def, substs) if def.repr.transparent() => {
+                    match self.transparent_field(def, substs) {
+                        Some(field) => ty = field,
+                        None => return Ok(()),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Returns the C declaration of `declarator` with the type `ty`, which is
+    /// `const`-qualified if `is_const` is set. The declarator may be empty, as
+    /// in a parameter list of a function pointer type.
+    fn declare(
+        &mut self,
+        ty: Ty<'tcx>,
+        declarator: &str,
+        is_const: bool,
+    ) -> Result<String, String> {
+        let tcx = self.tcx;
+        let name = match ty.kind {
+            ty::Bool => "bool".to_string(),
+            ty::Int(int_ty) => c_int_type(IntType::SignedInt(int_ty))
+                .ok_or_else(|| format!("`{}` has no C equivalent", ty))?
+                .to_string(),
+            ty::Uint(uint_ty) => c_int_type(IntType::UnsignedInt(uint_ty))
+                .ok_or_else(|| format!("`{}` has no C equivalent", ty))?
+                .to_string(),
+            ty::Float(ast::FloatTy::F32) => "float".to_string(),
+            ty::Float(ast::FloatTy::F64) => "double".to_string(),
+            ty::Char => {
+                return Err("`char` has no C equivalent, consider using `u32` instead".to_string());
+            }
+
+            ty::RawPtr(ty::TypeAndMut { ty: pointee, mutbl }) | ty::Ref(_, pointee, mutbl) => {
+                if !pointee.is_sized(tcx.at(DUMMY_SP), ParamEnv::reveal_all()) {
+                    return Err(format!("`{}` is a wide pointer, which has no C equivalent", ty));
+                }
+                let declarator = format!("*{}{}", if is_const { "const " } else { "" }, declarator);
+                let pointee_is_const = mutbl == ast::Mutability::Not;
+                if pointee.is_unit() || self.is_c_void(pointee) {
+                    return Ok(c_declaration("void", &declarator, pointee_is_const));
+                }
+                return self.declare(pointee, &declarator, pointee_is_const);
+            }
+
+            ty::FnPtr(sig) => {
+                let sig = tcx.normalize_erasing_late_bound_regions(ParamEnv::reveal_all(), &sig);
+                if sig.abi != Abi::C {
+                    return Err(format!("`{}` does not use the C ABI", ty));
+                }
+                let mut params = Vec::new();
+                for &input in sig.inputs() {
+                    params.push(self.declare_param(input, "")?);
+                }
+                let params = parameter_list(params, sig.c_variadic);
+                let declarator = format!(
+                    "(*{}{})({})",
+                    if is_const { "const " } else { "" },
+                    declarator,
+                    params
+                );
+                return self.declare_return(sig.output(), &declarator);
+            }
+
+            ty::Array(element, len) => {
+                let len = len.eval_usize(tcx, ParamEnv::reveal_all());
+                let declarator = if declarator.starts_with('*') {
+                    format!("({})[{}]", declarator, len)
+                } else {
+                    format!("{}[{}]", declarator, len)
+                };
+                return self.declare(element, &declarator, is_const);
+            }
+
+            ty::Adt(def, substs) => {
+                if self.is_c_void(ty) {
+                    return Err("`c_void` can only be used behind a pointer".to_string());
+                }
+                if def.repr.transparent() {
+                    let field = self.transparent_field(def, substs).ok_or_else(|| {
+                        format!("`{}` does not wrap a type with a C equivalent", ty)
+                    })?;
+                    return self.declare(field, declarator, is_const);
+                }
+                if let Some(pointer) = self.nullable_pointer(ty, def, substs) {
+                    return self.declare(pointer, declarator, is_const);
+                }
+                self.define_adt(ty, def, substs)?
+            }
+
+            _ => return Err(format!("`{}` has no C equivalent", ty)),
+        };
+
+        Ok(c_declaration(&name, declarator, is_const))
+    }
+
+    /// Emits the definition of a `#[repr(C)]` struct or union, or of a
+    /// fieldless enum, and returns its C name.
+    fn define_adt(
+        &mut self,
+        ty: Ty<'tcx>,
+        def: &'tcx AdtDef,
+        substs: SubstsRef<'tcx>,
+    ) -> Result<String, String> {
+        let tcx = self.tcx;
+        if let Some(name) = self.names.get(&ty) {
+            return Ok(name.clone());
+        }
+
+        if substs.types().next().is_some() {
+            return Err(format!("`{}` is generic, which cannot be expressed in C", ty));
+        }
+        let name = tcx.item_name(def.did).to_string();
+        check_c_identifier(&name, "type")?;
+        if let Some(&other) = self.types.get(&name) {
+            return Err(format!("`{}` and `{}` would both be named `{}` in C", other, ty, name));
+        }
+
+        let layout = tcx
+            .layout_of(ParamEnv::reveal_all().and(ty))
+            .map_err(|e| format!("the layout of `{}` cannot be computed: {}", ty, e))?;
+        let layout_comment =
+            format!("/* size: {}, align: {} */", layout.size.bytes(), layout.align.abi.bytes());
+
+        self.names.insert(ty, name.clone());
+        self.types.insert(name.clone(), ty);
+
+        let definition = match def.adt_kind() {
+            AdtKind::Struct | AdtKind::Union => {
+                if !def.repr.c() {
+                    return Err(format!(
+                        "`{}` has an unspecified layout, consider adding `#[repr(C)]`",
+                        ty
+                    ));
+                }
+                let keyword = if def.is_union() { "union" } else { "struct" };
+                self.forward_decls.push(format!("typedef {} {} {};", keyword, name, name));
+
+                let mut fields = String::new();
+                for field in &def.non_enum_variant().fields {
+                    let field_ty = self.field_ty(field, substs);
+                    if self.is_zst(field_ty) {
+                        // C has no zero-sized types, so they are left out,
+                        // which only keeps the layout of `#[repr(C)]` types
+                        // if they don't raise the alignment.
+                        if self.align(field_ty) > 1 {
+                            return Err(format!(
+                                "field `{}` of `{}` is zero-sized and aligned to {} bytes, \
+                                 which cannot be expressed in C",
+                                field.ident,
+                                ty,
+                                self.align(field_ty)
+                            ));
+                        }
+                        continue;
+                    }
+                    let field_name = field.ident.to_string();
+                    let field_name = if field_name.starts_with(|c: char| c.is_ascii_digit()) {
+                        format!("_{}", field_name)
+                    } else {
+                        field_name
+                    };
+                    check_c_identifier(&field_name, "field")?;
+                    let field_decl = self.declare(field_ty, &field_name, false)?;
+                    writeln!(fields, "    {};", field_decl).unwrap();
+                }
+                if fields.is_empty() {
+                    return Err(format!("`{}` has no fields, which is not allowed in C", ty));
+                }
+                format!("{}\n{} {} {{\n{}}};", layout_comment, keyword, name, fields)
+            }
+            AdtKind::Enum => {
+                if !def.is_payloadfree() {
+                    return Err(format!("`{}` has fields, which cannot be expressed in C", ty));
+                }
+                let mut variants = String::new();
+                for (variant_index, discr) in def.discriminants(tcx) {
+                    let variant = &def.variants[variant_index];
+                    writeln!(variants, "    {}_{} = {},", name, variant.ident, discr).unwrap();
+                }
+                match def.repr.int {
+                    // The size of an enum is implementation-defined in C, so
+                    // enums with an explicit integer representation are
+                    // declared as that integer type.
+                    Some(int) => {
+                        let int_name = c_int_type(int)
+                            .ok_or_else(|| format!("`{}` has no C equivalent", ty))?;
+                        format!(
+                            "{}\nenum {{\n{}}};\ntypedef {} {};",
+                            layout_comment, variants, int_name, name
+                        )
+                    }
+                    None if def.repr.c() => format!(
+                        "{}\ntypedef enum {} {{\n{}}} {};",
+                        layout_comment, name, variants, name
+                    ),
+                    None => {
+                        return Err(format!(
+                            "`{}` has an unspecified layout, consider adding `#[repr(C)]` \
+                             or an integer representation like `#[repr(u8)]`",
+                            ty
+                        ));
+                    }
+                }
+            }
+        };
+
+        self.definitions.push(definition);
+        Ok(name)
+    }
+
+    /// Returns the non-zero-sized field of a `#[repr(transparent)]` type.
+    fn transparent_field(&self, def: &'tcx AdtDef, substs: SubstsRef<'tcx>) -> Option<Ty<'tcx>> {
+        if def.is_enum() {
+            return None;
+        }
+        def.non_enum_variant()
+            .fields
+            .iter()
+            .map(|field| self.field_ty(field, substs))
+            .find(|&field_ty| !self.is_zst(field_ty))
+    }
+
+    /// Returns the pointer type wrapped by an `Option`-like enum such as
+    /// `Option<&T>` or `Option<extern "C" fn()>`, which is represented as a
+    /// nullable pointer.
+    fn nullable_pointer(
+        &self,
+        ty: Ty<'tcx>,
+        def: &'tcx AdtDef,
+        substs: SubstsRef<'tcx>,
+    ) -> Option<Ty<'tcx>> {
+        let tcx = self.tcx;
+        if !def.is_enum() || def.variants.len() != 2 || def.repr.inhibit_enum_layout_opt() {
+            return None;
+        }
+        let fields: Vec<_> = def.variants.iter().flat_map(|variant| &variant.fields).collect();
+        if fields.len() != 1 {
+            return None;
+        }
+        let field_ty = self.field_ty(fields[0], substs);
+        match field_ty.kind {
+            ty::Ref(..) | ty::FnPtr(..) => {}
+            _ => return None,
+        }
+        let size = |ty| tcx.layout_of(ParamEnv::reveal_all().and(ty)).ok().map(|l| l.size);
+        if size(ty)? != size(field_ty)? {
+            return None;
+        }
+        Some(field_ty)
+    }
+
+    fn field_ty(&self, field: &ty::FieldDef, substs: SubstsRef<'tcx>) -> Ty<'tcx> {
+        self.tcx.normalize_erasing_regions(ParamEnv::reveal_all(), field.ty(self.tcx, substs))
+    }
+
+    /// Returns whether `ty` is `core::ffi::c_void`, which `std` re-exports, or
+    /// the `c_void` that older versions of the `libc` crate define themselves.
+    fn is_c_void(&self, ty: Ty<'tcx>) -> bool {
+        let tcx = self.tcx;
+        let def = match ty.kind {
+            ty::Adt(def, _) => def,
+            _ => return false,
+        };
+        let expected_path: &[&str] = match &*tcx.crate_name(def.did.krate).as_str() {
+            "core" => &["ffi", "c_void"],
+            "libc" => &["c_void"],
+            _ => return false,
+        };
+        let path = tcx.def_path(def.did).data;
+        path.len() == expected_path.len()
+            && path
+                .iter()
+                .zip(expected_path)
+                .all(|(data, &name)| data.data.as_symbol().as_str() == name)
+    }
+
+    fn is_zst(&self, ty: Ty<'tcx>) -> bool {
+        self.tcx.layout_of(ParamEnv::reveal_all().and(ty)).map_or(false, |layout| layout.is_zst())
+    }
+
+    fn align(&self, ty: Ty<'tcx>) -> u64 {
+        self.tcx
+            .layout_of(ParamEnv::reveal_all().and(ty))
+            .map_or(1, |layout| layout.align.abi.bytes())
+    }
+}
+
+/// Keywords of C11 and C++17, which the header is included from as well, and
+/// the names defined by the headers it includes.
+#[rustfmt::skip]
+const C_RESERVED_NAMES: &[&str] = &[
+    "alignas", "alignof", "and", "and_eq", "asm", "auto", "bitand", "bitor", "bool", "break",
+    "case", "catch", "char", "char16_t", "char32_t", "class", "compl", "const", "const_cast",
+    "constexpr", "continue", "decltype", "default", "delete", "do", "double", "dynamic_cast",
+    "else", "enum", "explicit", "export", "extern", "false", "float", "for", "friend", "goto",
+    "if", "inline", "int", "int16_t", "int32_t", "int64_t", "int8_t", "intptr_t", "long",
+    "mutable", "namespace", "new", "noexcept", "not", "not_eq", "nullptr", "operator", "or",
+    "or_eq", "private", "protected", "public", "register", "reinterpret_cast", "restrict",
+    "return", "short", "signed", "sizeof", "static", "static_assert", "static_cast", "struct",
+    "switch", "template", "this", "thread_local", "throw", "true", "try", "typedef", "typeid",
+    "typename", "uint16_t", "uint32_t", "uint64_t", "uint8_t", "uintptr_t", "union",
+    "unsigned", "using", "virtual", "void", "volatile", "wchar_t", "while", "xor", "xor_eq",
+];
+
+/// Checks that `name` can be used as an identifier in C and C++: it must not
+/// be a keyword, and names starting with two underscores or an underscore and
+/// an uppercase letter are reserved for the implementation.
+fn check_c_identifier(name: &str, what: &str) -> Result<(), String> {
+    if C_RESERVED_NAMES.contains(&name) {
+        return Err(format!("the {} name `{}` is a keyword in C or C++", what, name));
+    }
+    let mut chars = name.chars();
+    if chars.next() == Some('_') && chars.next().map_or(false, |c| c == '_' || c.is_uppercase()) {
+        return Err(format!("the {} name `{}` is reserved in C and C++", what, name));
+    }
+    Ok(())
+}
+
+fn c_declaration(name: &str, declarator: &str, is_const: bool) -> String {
+    let qualifier = if is_const { "const " } else { "" };
+    if declarator.is_empty() {
+        format!("{}{}", qualifier, name)
+    } else {
+        format!("{}{} {}", qualifier, name, declarator)
+    }
+}
+
+fn parameter_list(params: Vec<String>, c_variadic: bool) -> String {
+    if params.is_empty() {
+        return if c_variadic { "...".to_string() } else { "void".to_string() };
+    }
+    let mut list = params.join(", ");
+    if c_variadic {
+        list.push_str(", ...");
+    }
+    list
+}
+
+fn c_int_type(int: IntType) -> Option<&'static str> {
+    Some(match int {
+        IntType::SignedInt(ast::IntTy::I8) => "int8_t",
+        IntType::SignedInt(ast::IntTy::I16) => "int16_t",
+        IntType::SignedInt(ast::IntTy::I32) => "int32_t",
+        IntType::SignedInt(ast::IntTy::I64) => "int64_t",
+        IntType::SignedInt(ast::IntTy::Isize) => "intptr_t",
+        IntType::UnsignedInt(ast::UintTy::U8) => "uint8_t",
+        IntType::UnsignedInt(ast::UintTy::U16) => "uint16_t",
+        IntType::UnsignedInt(ast::UintTy::U32) => "uint32_t",
+        IntType::UnsignedInt(ast::UintTy::U64) => "uint64_t",
+        IntType::UnsignedInt(ast::UintTy::Usize) => "uintptr_t",
+        IntType::SignedInt(ast::IntTy::I128) | IntType::UnsignedInt(ast::UintTy::U128) => {
+            return None;
+        }
+    })
+}
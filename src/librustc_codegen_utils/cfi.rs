@@ -0,0 +1,84 @@
+//! Type identifiers for `-Z sanitizer=cfi`.
+//!
+//! LLVM's control flow integrity checks are based on type metadata: every
+//! function and vtable entry that may be the target of an indirect call is
+//! tagged with an identifier of its type, and every indirect call checks that
+//! its target is tagged with the identifier the caller expects. The checks are
+//! resolved when the whole program is available, so identifiers have to be
+//! stable across crates. They are derived from the stable hash of the Rust
+//! type involved, which only depends on the def-path hashes of the items it
+//! refers to.
+//!
+//! Crates compiled with `-Z cfi-type-metadata`, like the standard library,
+//! tag their functions and vtables without checking their own indirect calls.
+//! Functions and vtables of crates compiled with neither flag can't be the
+//! target of checked calls, so such crates are rejected when loaded.
+
+use rustc::hir;
+use rustc::ich::{NodeIdHashingMode, StableHashingContext};
+use rustc::ty::{self, Instance, TyCtxt};
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+
+fn stable_hash<T>(tcx: TyCtxt<'tcx>, value: &T) -> Fingerprint
+where
+    T: for<'a> HashStable<StableHashingContext<'a>>,
+{
+    let mut hasher = StableHasher::new();
+    let mut hcx = tcx.create_stable_hashing_context();
+    hcx.while_hashing_spans(false, |hcx| {
+        hcx.with_node_id_hashing_mode(NodeIdHashingMode::HashDefPath, |hcx| {
+            value.hash_stable(hcx, &mut hasher);
+        });
+    });
+    hasher.finish()
+}
+
+/// The type identifier of functions called through a function pointer of
+/// signature `sig`.
+pub fn fn_ptr_typeid(tcx: TyCtxt<'tcx>, sig: ty::PolyFnSig<'tcx>) -> String {
+    let mut sig = tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), &sig);
+    // Safe functions can be called through `unsafe fn` pointers, and the
+    // distinction doesn't exist at runtime anyway.
+    sig.unsafety = hir::Unsafety::Normal;
+    format!("_R.cfi.fn.{}", stable_hash(tcx, &sig).to_hex())
+}
+
+/// The signature `instance` has when its address is taken as a function
+/// pointer, if it can be.
+pub fn instance_fn_ptr_sig(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+) -> Option<ty::PolyFnSig<'tcx>> {
+    if let ty::InstanceDef::ClosureOnceShim { .. } = instance.def {
+        // Non-capturing closures coerced to function pointers are called
+        // through this shim, which takes the arguments of the closure.
+        if let ty::Closure(def_id, substs) = instance.substs.type_at(0).kind {
+            let sig = substs.as_closure().sig(def_id, tcx);
+            return Some(tcx.coerce_closure_fn_ty(sig, hir::Unsafety::Normal).fn_sig(tcx));
+        }
+    }
+
+    let ty = instance.ty(tcx);
+    match ty.kind {
+        ty::FnDef(..) | ty::FnPtr(_) => Some(ty.fn_sig(tcx)),
+        _ => None,
+    }
+}
+
+/// The type identifier of entry `index` of the vtables of trait objects with
+/// principal trait `trait_ref`.
+///
+/// Vtable entries aren't identified by the signature of the method they hold,
+/// which refers to the concrete self type, but by their position in the vtable
+/// of a particular trait.
+pub fn vtable_entry_typeid(
+    tcx: TyCtxt<'tcx>,
+    trait_ref: Option<ty::PolyExistentialTraitRef<'tcx>>,
+    index: u64,
+) -> String {
+    let trait_ref = trait_ref.map(|trait_ref| {
+        tcx.anonymize_late_bound_regions(&tcx.erase_regions(&trait_ref))
+    });
+    format!("_R.cfi.vtable.{}.{}", stable_hash(tcx, &trait_ref).to_hex(), index)
+}
@@ -20,6 +20,8 @@ use rustc::ty::query::Providers;
 use rustc::ty::TyCtxt;
 use syntax::symbol::sym;
 
+pub mod c_header;
+pub mod cfi;
 pub mod codegen_backend;
 pub mod link;
 pub mod symbol_names;
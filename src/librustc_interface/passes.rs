@@ -181,6 +181,9 @@ pub fn register_plugins<'a>(
     sess.init_features(features);
 
     let crate_types = util::collect_crate_types(sess, &krate.attrs);
+    if sess.opts.crate_types.is_empty() {
+        sess.check_staticlib_dce(&crate_types);
+    }
     sess.crate_types.set(crate_types);
 
     let disambiguator = util::compute_crate_disambiguator(sess);
@@ -544,6 +547,7 @@ fn escape_dep_filename(filename: &FileName) -> String {
 
 fn write_out_deps(
     sess: &Session,
+    krate: &ast::Crate,
     boxed_resolver: &Steal<Rc<RefCell<BoxedResolver>>>,
     outputs: &OutputFilenames,
     out_filenames: &[PathBuf],
@@ -566,6 +570,11 @@ fn write_out_deps(
             .map(|fmap| escape_dep_filename(&fmap.unmapped_path.as_ref().unwrap_or(&fmap.name)))
             .collect();
 
+        // The scripts embedded with `#[debugger_visualizer]` are inputs too.
+        for path in rustc_metadata::debugger_visualizers::script_paths(sess, &krate.attrs) {
+            files.push(escape_dep_filename(&FileName::Real(path)));
+        }
+
         if sess.binary_dep_depinfo() {
             boxed_resolver.borrow().borrow_mut().access(|resolver| {
                 for cnum in resolver.cstore().crates_untracked() {
@@ -655,7 +664,7 @@ pub fn prepare_outputs(
         }
     }
 
-    write_out_deps(sess, boxed_resolver, &outputs, &output_paths);
+    write_out_deps(sess, krate, boxed_resolver, &outputs, &output_paths);
 
     let only_dep_info = sess.opts.output_types.contains_key(&OutputType::DepInfo)
         && sess.opts.output_types.len() == 1;
@@ -980,6 +989,15 @@ pub fn start_codegen<'tcx>(
     let (metadata, need_metadata_module) =
         time(tcx.sess, "metadata encoding and writing", || encode_and_write_metadata(tcx, outputs));
 
+    // Errors found while writing the header abort the compilation, so do it
+    // before spending any time in codegen.
+    if tcx.sess.opts.output_types.contains_key(&OutputType::CHeader) {
+        if let Err(e) = rustc_codegen_utils::c_header::emit_c_header(tcx, outputs) {
+            tcx.sess.err(&format!("could not emit C header: {}", e));
+        }
+        tcx.sess.abort_if_errors();
+    }
+
     let codegen = time(tcx.sess, "codegen", move || {
         let _prof_timer = tcx.prof.generic_activity("codegen_crate");
         codegen_backend.codegen_crate(tcx, metadata, need_metadata_module)
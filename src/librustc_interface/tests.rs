@@ -7,7 +7,8 @@ use rustc::middle::cstore;
 use rustc::session::config::{build_configuration, build_session_options, to_crate_config};
 use rustc::session::config::{rustc_optgroups, ErrorOutputType, ExternLocation, Options, Passes};
 use rustc::session::config::{ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
-use rustc::session::config::{Externs, OutputType, OutputTypes, SymbolManglingVersion};
+use rustc::session::config::{Externs, OutputType, OutputTypes, SplitDebuginfo};
+use rustc::session::config::SymbolManglingVersion;
 use rustc::session::search_paths::SearchPath;
 use rustc::session::{build_session, Session};
 use rustc_data_structures::fx::FxHashSet;
@@ -632,6 +633,14 @@ fn test_debugging_options_tracking_hash() {
     opts = reference.clone();
     opts.debugging_opts.symbol_mangling_version = SymbolManglingVersion::V0;
     assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.cross_lang_lto = true;
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.split_debuginfo = SplitDebuginfo::Unpacked;
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
 }
 
 #[test]
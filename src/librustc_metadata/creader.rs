@@ -678,16 +678,22 @@ impl<'a> CrateLoader<'a> {
             // executables linked to `std`
             const ASAN_SUPPORTED_TARGETS: &[&str] =
                 &["x86_64-unknown-linux-gnu", "x86_64-apple-darwin"];
+            const KASAN_SUPPORTED_TARGETS: &[&str] = &["x86_64-linux-kernel"];
+            const HWASAN_SUPPORTED_TARGETS: &[&str] = &["aarch64-unknown-linux-gnu"];
             const TSAN_SUPPORTED_TARGETS: &[&str] =
                 &["x86_64-unknown-linux-gnu", "x86_64-apple-darwin"];
             const LSAN_SUPPORTED_TARGETS: &[&str] = &["x86_64-unknown-linux-gnu"];
             const MSAN_SUPPORTED_TARGETS: &[&str] = &["x86_64-unknown-linux-gnu"];
+            const CFI_SUPPORTED_TARGETS: &[&str] = &["x86_64-unknown-linux-gnu"];
 
             let supported_targets = match *sanitizer {
                 Sanitizer::Address => ASAN_SUPPORTED_TARGETS,
+                Sanitizer::KernelAddress => KASAN_SUPPORTED_TARGETS,
+                Sanitizer::HwAddress => HWASAN_SUPPORTED_TARGETS,
                 Sanitizer::Thread => TSAN_SUPPORTED_TARGETS,
                 Sanitizer::Leak => LSAN_SUPPORTED_TARGETS,
                 Sanitizer::Memory => MSAN_SUPPORTED_TARGETS,
+                Sanitizer::Cfi => CFI_SUPPORTED_TARGETS,
             };
             if !supported_targets.contains(&&*self.sess.opts.target_triple.triple()) {
                 self.sess.err(&format!(
@@ -698,10 +704,21 @@ impl<'a> CrateLoader<'a> {
                 return;
             }
 
+            if *sanitizer == Sanitizer::Cfi {
+                self.check_cfi_type_metadata();
+            }
+
+            // Failed control flow integrity checks trap, and the kernel
+            // provides its own address sanitizer runtime, so there is no
+            // runtime to link.
+            if *sanitizer == Sanitizer::Cfi || *sanitizer == Sanitizer::KernelAddress {
+                return;
+            }
+
             // firstyear 2017 - during testing I was unable to access an OSX machine
             // to make this work on different crate types. As a result, today I have
             // only been able to test and support linux as a target.
-            if self.sess.opts.target_triple.triple() == "x86_64-unknown-linux-gnu" {
+            if self.sess.target.target.target_os == "linux" {
                 if !self.sess.crate_types.borrow().iter().all(|ct| {
                     match *ct {
                         // Link the runtime
@@ -755,9 +772,11 @@ impl<'a> CrateLoader<'a> {
             if uses_std {
                 let name = Symbol::intern(match sanitizer {
                     Sanitizer::Address => "rustc_asan",
+                    Sanitizer::HwAddress => "rustc_hwasan",
                     Sanitizer::Leak => "rustc_lsan",
                     Sanitizer::Memory => "rustc_msan",
                     Sanitizer::Thread => "rustc_tsan",
+                    Sanitizer::KernelAddress | Sanitizer::Cfi => unreachable!(),
                 });
                 info!("loading sanitizer: {}", name);
 
@@ -774,8 +793,42 @@ impl<'a> CrateLoader<'a> {
         }
     }
 
+    /// Indirect calls checked by `-Z sanitizer=cfi` trap if their target isn't
+    /// tagged with the type the caller expects, which includes functions and
+    /// vtables of crates that were compiled without type metadata, even if
+    /// they are called correctly.
+    fn check_cfi_type_metadata(&self) {
+        let mut untagged = Vec::new();
+        self.cstore.iter_crate_data(|_, data| {
+            // The compiler builtins are only called directly.
+            if !data.has_cfi_type_metadata()
+                && !data.is_proc_macro_crate()
+                && !data.is_compiler_builtins()
+            {
+                untagged.push(format!("`{}`", data.name()));
+            }
+        });
+        if !untagged.is_empty() {
+            self.sess
+                .struct_err(&format!(
+                    "`-Z sanitizer=cfi` requires all crates to be compiled with \
+                     `-Z sanitizer=cfi` or `-Z cfi-type-metadata`, which {} {} not",
+                    untagged.join(", "),
+                    if untagged.len() == 1 { "was" } else { "were" }
+                ))
+                .note(
+                    "the standard library is compiled with `-Z cfi-type-metadata` by the \
+                     build system of rustc",
+                )
+                .emit();
+        }
+    }
+
     fn inject_profiler_runtime(&mut self) {
-        if self.sess.opts.debugging_opts.profile || self.sess.opts.cg.profile_generate.enabled() {
+        if self.sess.opts.debugging_opts.profile
+            || self.sess.opts.debugging_opts.instrument_coverage
+            || self.sess.opts.cg.profile_generate.enabled()
+        {
             info!("loading profiler");
 
             let name = Symbol::intern("profiler_builtins");
@@ -0,0 +1,104 @@
+use rustc::middle::cstore::DebuggerVisualizerFile;
+use rustc::session::Session;
+use rustc::ty::TyCtxt;
+use syntax::ast::{Attribute, NestedMetaItem};
+use syntax::source_map::{FileName, Span};
+use syntax::symbol::sym;
+
+use std::fs;
+use std::path::PathBuf;
+
+crate fn collect(tcx: TyCtxt<'_>) -> Vec<DebuggerVisualizerFile> {
+    let mut visualizers = Vec::new();
+    for (span, item) in visualizer_args(tcx.hir().krate_attrs()) {
+        match parse_gdb_script(&item) {
+            Some(name) => {
+                if let Some(file) = load(tcx.sess, span, name) {
+                    visualizers.push(file);
+                }
+            }
+            None => {
+                tcx.sess
+                    .struct_span_err(item.span(), "invalid argument to `debugger_visualizer`")
+                    .note(r#"expected `gdb_script = "file.py"`"#)
+                    .emit();
+            }
+        }
+    }
+    visualizers
+}
+
+/// Returns the paths of the scripts the crate embeds, for the dep-info.
+///
+/// The scripts are only read when the metadata is encoded, after the dep-info
+/// has been written, so unlike `include_str!` they don't get there through the
+/// source map. Invalid attributes are skipped, they are reported by `collect`.
+pub fn script_paths(sess: &Session, krate_attrs: &[Attribute]) -> Vec<PathBuf> {
+    visualizer_args(krate_attrs)
+        .iter()
+        .filter_map(|(span, item)| resolve(sess, *span, &parse_gdb_script(item)?).ok())
+        .collect()
+}
+
+/// Returns the arguments of every `#[debugger_visualizer]` attribute, along
+/// with the span of the attribute.
+fn visualizer_args(attrs: &[Attribute]) -> Vec<(Span, NestedMetaItem)> {
+    attrs
+        .iter()
+        .filter(|attr| attr.check_name(sym::debugger_visualizer))
+        .flat_map(|attr| {
+            let span = attr.span;
+            attr.meta_item_list().unwrap_or_default().into_iter().map(move |item| (span, item))
+        })
+        .collect()
+}
+
+fn parse_gdb_script(item: &NestedMetaItem) -> Option<String> {
+    if item.check_name(sym::gdb_script) {
+        item.value_str().map(|name| name.to_string())
+    } else {
+        None
+    }
+}
+
+/// Resolves the path of a script relative to the file containing the
+/// attribute like `include_str!` does, or returns the name of the non-file
+/// source the attribute is in.
+fn resolve(sess: &Session, span: Span, name: &str) -> Result<PathBuf, FileName> {
+    let mut path = match sess.source_map().span_to_unmapped_path(span) {
+        FileName::Real(path) => path,
+        FileName::DocTest(path, _) => path,
+        other => return Err(other),
+    };
+    path.pop();
+    path.push(name);
+    Ok(path)
+}
+
+/// Reads a script named by a `#[debugger_visualizer]` attribute.
+fn load(sess: &Session, span: Span, name: String) -> Option<DebuggerVisualizerFile> {
+    let path = match resolve(sess, span, &name) {
+        Ok(path) => path,
+        Err(other) => {
+            sess.span_err(
+                span,
+                &format!("cannot resolve relative path in non-file source `{}`", other),
+            );
+            return None;
+        }
+    };
+
+    match fs::read(&path) {
+        // The script ends up in a NUL-terminated entry of the section gdb
+        // loads it from.
+        Ok(ref src) if src.contains(&0) => {
+            sess.span_err(span, &format!("{} contains a NUL byte", path.display()));
+            None
+        }
+        Ok(src) => Some(DebuggerVisualizerFile { name, src }),
+        Err(e) => {
+            sess.span_err(span, &format!("couldn't read {}: {}", path.display(), e));
+            None
+        }
+    }
+}
@@ -32,6 +32,7 @@ mod native_libs;
 mod rmeta;
 
 pub mod creader;
+pub mod debugger_visualizers;
 pub mod dynamic_lib;
 pub mod locator;
 
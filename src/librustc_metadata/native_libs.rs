@@ -55,6 +55,7 @@ impl ItemLikeVisitor<'tcx> for Collector<'tcx> {
                 cfg: None,
                 foreign_module: Some(self.tcx.hir().local_def_id(it.hir_id)),
                 wasm_import_module: None,
+                span: Some(m.span),
             };
             let mut kind_specified = false;
 
@@ -258,6 +259,7 @@ impl Collector<'tcx> {
                     cfg: None,
                     foreign_module: None,
                     wasm_import_module: None,
+                    span: None,
                 };
                 self.register_native_lib(None, lib);
             } else {
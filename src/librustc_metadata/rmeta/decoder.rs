@@ -9,7 +9,7 @@ use rustc::hir::def::{self, CtorKind, CtorOf, DefKind, Res};
 use rustc::hir::def_id::{CrateNum, DefId, DefIndex, LocalDefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc::hir::map::definitions::DefPathTable;
 use rustc::hir::map::{DefKey, DefPath, DefPathData, DefPathHash};
-use rustc::middle::cstore::{CrateSource, ExternCrate};
+use rustc::middle::cstore::{CrateSource, DebuggerVisualizerFile, ExternCrate};
 use rustc::middle::cstore::{ForeignModule, LinkagePreference, NativeLibrary};
 use rustc::middle::exported_symbols::{ExportedSymbol, SymbolExportLevel};
 use rustc::middle::lang_items;
@@ -708,6 +708,7 @@ impl<'a, 'tcx> CrateMetadata {
                     data.paren_sugar,
                     data.has_auto_impl,
                     data.is_marker,
+                    data.specialization_kind,
                     self.def_path_table.def_path_hash(item_id),
                 )
             }
@@ -717,6 +718,7 @@ impl<'a, 'tcx> CrateMetadata {
                 false,
                 false,
                 false,
+                ty::TraitSpecializationKind::None,
                 self.def_path_table.def_path_hash(item_id),
             ),
             _ => bug!("def-index does not refer to trait or trait alias"),
@@ -1290,6 +1292,15 @@ impl<'a, 'tcx> CrateMetadata {
         }
     }
 
+    fn get_debugger_visualizers(&self, sess: &Session) -> Vec<DebuggerVisualizerFile> {
+        if self.root.is_proc_macro_crate() {
+            // Proc macro crates are never linked into the output.
+            vec![]
+        } else {
+            self.root.debugger_visualizers.decode((self, sess)).collect()
+        }
+    }
+
     fn get_foreign_modules(&self, tcx: TyCtxt<'tcx>) -> &'tcx [ForeignModule] {
         if self.root.is_proc_macro_crate() {
             // Proc macro crates do not have any *target* foreign modules.
@@ -1599,6 +1610,14 @@ impl<'a, 'tcx> CrateMetadata {
         self.root.profiler_runtime
     }
 
+    crate fn is_compiler_builtins(&self) -> bool {
+        self.root.compiler_builtins
+    }
+
+    crate fn has_cfi_type_metadata(&self) -> bool {
+        self.root.cfi_type_metadata
+    }
+
     crate fn needs_allocator(&self) -> bool {
         self.root.needs_allocator
     }
@@ -1,4 +1,5 @@
 use crate::creader::{CStore, LoadedMacro};
+use crate::debugger_visualizers;
 use crate::foreign_modules;
 use crate::link_args;
 use crate::native_libs;
@@ -188,6 +189,7 @@ provide! { <'tcx> tcx, def_id, other, cdata,
         tcx.arena.alloc(reachable_non_generics)
     }
     native_libraries => { Lrc::new(cdata.get_native_libraries(tcx.sess)) }
+    debugger_visualizers => { Lrc::new(cdata.get_debugger_visualizers(tcx.sess)) }
     foreign_modules => { cdata.get_foreign_modules(tcx) }
     plugin_registrar_fn => {
         cdata.root.plugin_registrar_fn.map(|index| {
@@ -289,6 +291,10 @@ pub fn provide(providers: &mut Providers<'_>) {
             assert_eq!(cnum, LOCAL_CRATE);
             Lrc::new(native_libs::collect(tcx))
         },
+        debugger_visualizers: |tcx, cnum| {
+            assert_eq!(cnum, LOCAL_CRATE);
+            Lrc::new(debugger_visualizers::collect(tcx))
+        },
         foreign_modules: |tcx, cnum| {
             assert_eq!(cnum, LOCAL_CRATE);
             &tcx.arena.alloc(foreign_modules::collect(tcx))[..]
@@ -5,7 +5,9 @@ use rustc::hir::def::CtorKind;
 use rustc::hir::def_id::{CrateNum, DefId, DefIndex, LocalDefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc::hir::map::definitions::DefPathTable;
 use rustc::hir::{AnonConst, GenericParamKind};
-use rustc::middle::cstore::{EncodedMetadata, ForeignModule, LinkagePreference, NativeLibrary};
+use rustc::middle::cstore::{
+    DebuggerVisualizerFile, EncodedMetadata, ForeignModule, LinkagePreference, NativeLibrary,
+};
 use rustc::middle::dependency_format::Linkage;
 use rustc::middle::exported_symbols::{metadata_symbol_name, ExportedSymbol, SymbolExportLevel};
 use rustc::middle::lang_items;
@@ -421,6 +423,8 @@ impl<'tcx> EncodeContext<'tcx> {
 
         let foreign_modules = self.encode_foreign_modules();
 
+        let debugger_visualizers = self.encode_debugger_visualizers();
+
         // Encode source_map
         i = self.position();
         let source_map = self.encode_source_map();
@@ -510,6 +514,7 @@ impl<'tcx> EncodeContext<'tcx> {
                 None
             },
             compiler_builtins: attr::contains_name(&attrs, sym::compiler_builtins),
+            cfi_type_metadata: tcx.sess.emits_cfi_type_metadata(),
             needs_allocator: attr::contains_name(&attrs, sym::needs_allocator),
             needs_panic_runtime: attr::contains_name(&attrs, sym::needs_panic_runtime),
             no_builtins: attr::contains_name(&attrs, sym::no_builtins),
@@ -526,6 +531,7 @@ impl<'tcx> EncodeContext<'tcx> {
             lang_items_missing,
             native_libraries,
             foreign_modules,
+            debugger_visualizers,
             source_map,
             def_path_table,
             impls,
@@ -1117,6 +1123,7 @@ impl EncodeContext<'tcx> {
                     paren_sugar: trait_def.paren_sugar,
                     has_auto_impl: self.tcx.trait_is_auto(def_id),
                     is_marker: trait_def.is_marker,
+                    specialization_kind: trait_def.specialization_kind,
                 };
 
                 EntryKind::Trait(self.lazy(data))
@@ -1321,6 +1328,11 @@ impl EncodeContext<'tcx> {
         self.lazy(used_libraries.iter().cloned())
     }
 
+    fn encode_debugger_visualizers(&mut self) -> Lazy<[DebuggerVisualizerFile]> {
+        let debugger_visualizers = self.tcx.debugger_visualizers(LOCAL_CRATE);
+        self.lazy(debugger_visualizers.iter().cloned())
+    }
+
     fn encode_foreign_modules(&mut self) -> Lazy<[ForeignModule]> {
         let foreign_modules = self.tcx.foreign_modules(LOCAL_CRATE);
         self.lazy(foreign_modules.iter().cloned())
@@ -4,7 +4,9 @@ use table::{Table, TableBuilder};
 use rustc::hir;
 use rustc::hir::def::{self, CtorKind};
 use rustc::hir::def_id::{DefId, DefIndex};
-use rustc::middle::cstore::{DepKind, ForeignModule, LinkagePreference, NativeLibrary};
+use rustc::middle::cstore::{
+    DebuggerVisualizerFile, DepKind, ForeignModule, LinkagePreference, NativeLibrary,
+};
 use rustc::middle::exported_symbols::{ExportedSymbol, SymbolExportLevel};
 use rustc::middle::lang_items;
 use rustc::mir;
@@ -190,6 +192,7 @@ crate struct CrateRoot<'tcx> {
     diagnostic_items: Lazy<[(Symbol, DefIndex)]>,
     native_libraries: Lazy<[NativeLibrary]>,
     foreign_modules: Lazy<[ForeignModule]>,
+    debugger_visualizers: Lazy<[DebuggerVisualizerFile]>,
     source_map: Lazy<[syntax_pos::SourceFile]>,
     def_path_table: Lazy<hir::map::definitions::DefPathTable>,
     impls: Lazy<[TraitImpls]>,
@@ -202,6 +205,7 @@ crate struct CrateRoot<'tcx> {
     proc_macro_data: Option<Lazy<[DefIndex]>>,
 
     compiler_builtins: bool,
+    cfi_type_metadata: bool,
     needs_allocator: bool,
     needs_panic_runtime: bool,
     no_builtins: bool,
@@ -347,6 +351,7 @@ struct TraitData {
     paren_sugar: bool,
     has_auto_impl: bool,
     is_marker: bool,
+    specialization_kind: ty::TraitSpecializationKind,
 }
 
 #[derive(RustcEncodable, RustcDecodable)]
@@ -112,6 +112,9 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
                 self.write_scalar(location.ptr, dest)?;
             }
 
+            // Coverage counters are only meaningful in generated code.
+            sym::count_code_region => {}
+
             sym::min_align_of
             | sym::pref_align_of
             | sym::needs_drop
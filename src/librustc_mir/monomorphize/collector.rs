@@ -574,7 +574,7 @@ impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
                             ty::ClosureKind::FnOnce,
                         );
                         if should_monomorphize_locally(self.tcx, &instance) {
-                            self.output.push(create_fn_mono_item(instance));
+                            self.output.push(create_fn_mono_item(self.tcx, instance));
                         }
                     }
                     _ => bug!(),
@@ -588,7 +588,7 @@ impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
                     .unwrap_or_else(|e| tcx.sess.fatal(&e));
                 let instance = Instance::mono(tcx, exchange_malloc_fn_def_id);
                 if should_monomorphize_locally(tcx, &instance) {
-                    self.output.push(create_fn_mono_item(instance));
+                    self.output.push(create_fn_mono_item(tcx, instance));
                 }
             }
             _ => { /* not interesting */ }
@@ -727,7 +727,7 @@ fn visit_instance_use<'tcx>(
         ty::InstanceDef::DropGlue(_, None) => {
             // Don't need to emit noop drop glue if we are calling directly.
             if !is_direct_call {
-                output.push(create_fn_mono_item(instance));
+                output.push(create_fn_mono_item(tcx, instance));
             }
         }
         ty::InstanceDef::DropGlue(_, Some(_))
@@ -737,7 +737,7 @@ fn visit_instance_use<'tcx>(
         | ty::InstanceDef::Item(..)
         | ty::InstanceDef::FnPtrShim(..)
         | ty::InstanceDef::CloneShim(..) => {
-            output.push(create_fn_mono_item(instance));
+            output.push(create_fn_mono_item(tcx, instance));
         }
     }
 }
@@ -769,7 +769,7 @@ fn should_monomorphize_locally<'tcx>(tcx: TyCtxt<'tcx>, instance: &Instance<'tcx
     }
 
     if tcx.is_reachable_non_generic(def_id)
-        || is_available_upstream_generic(tcx, def_id, instance.substs)
+        || is_available_upstream_generic(tcx, def_id, instance.polymorphize(tcx).substs)
     {
         // We can link to the item in question, no instance needed
         // in this crate.
@@ -913,9 +913,9 @@ fn find_vtable_types_for_unsizing<'tcx>(
     }
 }
 
-fn create_fn_mono_item(instance: Instance<'_>) -> MonoItem<'_> {
+fn create_fn_mono_item<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> MonoItem<'tcx> {
     debug!("create_fn_mono_item(instance={})", instance);
-    MonoItem::Fn(instance)
+    MonoItem::Fn(instance.polymorphize(tcx))
 }
 
 /// Creates a `MonoItem` for each method that is referenced by the vtable for
@@ -954,7 +954,7 @@ fn create_mono_items_for_vtable_methods<'tcx>(
                     .unwrap()
                 })
                 .filter(|&instance| should_monomorphize_locally(tcx, &instance))
-                .map(|instance| create_fn_mono_item(instance));
+                .map(|instance| create_fn_mono_item(tcx, instance));
             output.extend(methods);
         }
 
@@ -1080,7 +1080,7 @@ impl RootCollector<'_, 'v> {
             debug!("RootCollector::push_if_root: found root def_id={:?}", def_id);
 
             let instance = Instance::mono(self.tcx, def_id);
-            self.output.push(create_fn_mono_item(instance));
+            self.output.push(create_fn_mono_item(self.tcx, instance));
         }
     }
 
@@ -1116,7 +1116,7 @@ impl RootCollector<'_, 'v> {
         )
         .unwrap();
 
-        self.output.push(create_fn_mono_item(start_instance));
+        self.output.push(create_fn_mono_item(self.tcx, start_instance));
     }
 }
 
@@ -1172,10 +1172,12 @@ fn create_mono_items_for_default_impls<'tcx>(
                     let instance =
                         ty::Instance::resolve(tcx, param_env, method.def_id, substs).unwrap();
 
-                    let mono_item = create_fn_mono_item(instance);
-                    if mono_item.is_instantiable(tcx) && should_monomorphize_locally(tcx, &instance)
+                    // Test the predicates before polymorphizing the instance,
+                    // they cannot hold for the parameters it leaves in place.
+                    if MonoItem::Fn(instance).is_instantiable(tcx)
+                        && should_monomorphize_locally(tcx, &instance)
                     {
-                        output.push(mono_item);
+                        output.push(create_fn_mono_item(tcx, instance));
                     }
                 }
             }
@@ -1204,7 +1206,7 @@ fn collect_miri<'tcx>(tcx: TyCtxt<'tcx>, alloc_id: AllocId, output: &mut Vec<Mon
         Some(GlobalAlloc::Function(fn_instance)) => {
             if should_monomorphize_locally(tcx, &fn_instance) {
                 trace!("collecting {:?} with {:#?}", alloc_id, fn_instance);
-                output.push(create_fn_mono_item(fn_instance));
+                output.push(create_fn_mono_item(tcx, fn_instance));
             }
         }
         None => bug!("alloc id without corresponding allocation: {}", alloc_id),
@@ -4,6 +4,7 @@ use rustc::ty::{self, Ty, TyCtxt};
 
 pub mod collector;
 pub mod partitioning;
+pub mod polymorphize;
 
 pub fn custom_coerce_unsize_info<'tcx>(
     tcx: TyCtxt<'tcx>,
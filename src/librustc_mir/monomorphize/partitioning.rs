@@ -96,12 +96,14 @@ use std::cmp;
 use std::collections::hash_map::Entry;
 use std::sync::Arc;
 
+use rustc::dep_graph::{DepKind, WorkProductId};
 use rustc::hir::def::DefKind;
 use rustc::hir::def_id::{CrateNum, DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc::hir::CodegenFnAttrFlags;
 use rustc::middle::exported_symbols::SymbolExportLevel;
 use rustc::mir::mono::{CodegenUnit, CodegenUnitNameBuilder, Linkage, Visibility};
 use rustc::mir::mono::{InstantiationMode, MonoItem};
+use rustc::session::config::CguPartitioning;
 use rustc::ty::print::characteristic_def_id_of_type;
 use rustc::ty::query::Providers;
 use rustc::ty::{self, DefIdTree, InstanceDef, TyCtxt};
@@ -118,6 +120,10 @@ pub enum PartitioningStrategy {
 
     /// Partition the whole crate into a fixed number of codegen units.
     FixedUnitCount(usize),
+
+    /// Partition the whole crate into a fixed number of codegen units, keeping
+    /// codegen units that reference each other together where possible.
+    CallGraph(usize),
 }
 
 // Anything we can't find a proper codegen unit for goes into this.
@@ -144,16 +150,69 @@ where
         place_root_mono_items(tcx, mono_items)
     };
 
+    if tcx.sess.opts.debugging_opts.incremental_delta_cgus && tcx.dep_graph.is_fully_enabled() {
+        let _prof_timer = tcx.prof.generic_activity("cgu_partitioning_isolate_changed_items");
+        isolate_changed_items(tcx, &mut initial_partitioning);
+    }
+
     initial_partitioning.codegen_units.iter_mut().for_each(|cgu| cgu.estimate_size(tcx));
 
     debug_dump(tcx, "INITIAL PARTITIONING:", initial_partitioning.codegen_units.iter());
 
     // If the partitioning should produce a fixed count of codegen units, merge
     // until that count is reached.
-    if let PartitioningStrategy::FixedUnitCount(count) = strategy {
-        let _prof_timer = tcx.prof.generic_activity("cgu_partitioning_merge_cgus");
-        merge_codegen_units(tcx, &mut initial_partitioning, count);
-        debug_dump(tcx, "POST MERGING:", initial_partitioning.codegen_units.iter());
+    match strategy {
+        PartitioningStrategy::PerModule => {}
+        PartitioningStrategy::FixedUnitCount(count) => {
+            let _prof_timer = tcx.prof.generic_activity("cgu_partitioning_merge_cgus");
+            merge_codegen_units(tcx, &mut initial_partitioning.codegen_units, count);
+            debug_dump(tcx, "POST MERGING:", initial_partitioning.codegen_units.iter());
+
+            if tcx.sess.opts.debugging_opts.cgu_partitioning_stats {
+                let references =
+                    count_cross_cgu_references(&initial_partitioning.codegen_units, inlining_map);
+                println!("CGU_PARTITIONING {} references between codegen units", references);
+            }
+        }
+        PartitioningStrategy::CallGraph(count) => {
+            let _prof_timer = tcx.prof.generic_activity("cgu_partitioning_merge_cgus");
+
+            // Merge a copy by size as well, to compare the two strategies.
+            let size_based = if tcx.sess.opts.debugging_opts.cgu_partitioning_stats {
+                let mut codegen_units: Vec<_> = initial_partitioning
+                    .codegen_units
+                    .iter()
+                    .map(|cgu| {
+                        let mut copy = CodegenUnit::new(cgu.name());
+                        copy.items_mut().extend(cgu.items().iter().map(|(&k, &v)| (k, v)));
+                        copy.estimate_size(tcx);
+                        copy
+                    })
+                    .collect();
+                merge_codegen_units(tcx, &mut codegen_units, count);
+                Some(count_cross_cgu_references(&codegen_units, inlining_map))
+            } else {
+                None
+            };
+
+            merge_codegen_units_by_call_graph(
+                tcx,
+                &mut initial_partitioning.codegen_units,
+                count,
+                inlining_map,
+            );
+            debug_dump(tcx, "POST MERGING:", initial_partitioning.codegen_units.iter());
+
+            if let Some(size_based) = size_based {
+                let references =
+                    count_cross_cgu_references(&initial_partitioning.codegen_units, inlining_map);
+                println!(
+                    "CGU_PARTITIONING {} references between codegen units \
+                     ({} with size-based merging)",
+                    references, size_based
+                );
+            }
+        }
     }
 
     // In the next step, we use the inlining map to determine which additional
@@ -280,6 +339,97 @@ where
     }
 }
 
+/// Implements `-Z incremental-delta-cgus`: moves each function that changed
+/// since the previous session out of its per-module codegen unit and into a
+/// "delta" codegen unit of its own. Functions that were moved out in a
+/// previous session stay in their delta unit, so that the rest of the module
+/// keeps the same contents from one session to the next. Editing a function
+/// then invalidates the module's codegen unit only the first time; after that
+/// only the small delta unit has to go through LLVM again, while the object
+/// code for the rest of the module is reused. Functions go back to their
+/// module once it has to be codegened again for another reason, which costs
+/// nothing extra, so that delta units don't pile up for functions that are
+/// no longer edited.
+fn isolate_changed_items<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    initial_partitioning: &mut PreInliningPartitioning<'tcx>,
+) {
+    let cgu_name_builder = &mut CodegenUnitNameBuilder::new(tcx);
+    let mut delta_cgus = Vec::new();
+
+    for cgu in &mut initial_partitioning.codegen_units {
+        // If there is nothing to reuse for this codegen unit, there is no point
+        // in moving anything out of it that has not been moved out before.
+        let has_work_product =
+            tcx.dep_graph.previous_work_product(&cgu.work_product_id()).is_some();
+
+        let mut isolated = Vec::new();
+        let mut unchanged_isolated = Vec::new();
+        // Whether the codegen unit has to be codegened again anyway, in which
+        // case the unchanged functions of its delta units go back into it.
+        let mut cgu_changed = !has_work_product;
+
+        for &mono_item in cgu.items().keys() {
+            let def_id = match mono_item {
+                MonoItem::Fn(instance) => match instance.def {
+                    InstanceDef::Item(def_id) if def_id.is_local() => def_id,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let changed = !tcx.dep_graph.is_green(&def_id.to_dep_node(tcx, DepKind::optimized_mir));
+            if mono_item.is_generic_fn() {
+                cgu_changed |= changed;
+                continue;
+            }
+
+            let delta_cgu_name = delta_cgu_name(tcx, cgu_name_builder, def_id);
+            let work_product_id = WorkProductId::from_cgu_name(&delta_cgu_name.as_str());
+            let was_isolated = tcx.dep_graph.previous_work_product(&work_product_id).is_some();
+
+            // A function that changed for the first time changes the codegen
+            // unit, whether it is moved out of it or not.
+            cgu_changed |= changed && !was_isolated;
+
+            if changed && (was_isolated || has_work_product) {
+                isolated.push((mono_item, delta_cgu_name));
+            } else if was_isolated {
+                unchanged_isolated.push((mono_item, delta_cgu_name));
+            }
+        }
+
+        if !cgu_changed {
+            isolated.extend(unchanged_isolated);
+        }
+
+        for (mono_item, delta_cgu_name) in isolated {
+            debug!("moving {:?} out of {} into {}", mono_item, cgu.name(), delta_cgu_name);
+            let linkage_and_visibility = cgu.items_mut().remove(&mono_item).unwrap();
+            let mut delta_cgu = CodegenUnit::new(delta_cgu_name);
+            delta_cgu.items_mut().insert(mono_item, linkage_and_visibility);
+            delta_cgus.push(delta_cgu);
+        }
+    }
+
+    initial_partitioning.codegen_units.extend(delta_cgus);
+}
+
+/// Names the delta codegen unit of a function after its path, e.g.
+/// `krate-m-edited.delta`, so that the name stays the same from one session
+/// to the next.
+fn delta_cgu_name(
+    tcx: TyCtxt<'_>,
+    name_builder: &mut CodegenUnitNameBuilder<'_>,
+    def_id: DefId,
+) -> Symbol {
+    let def_path = tcx.def_path(def_id);
+    let components = def_path.data.iter().map(|part| match part.disambiguator {
+        0 => part.data.as_symbol().to_string(),
+        disambiguator => format!("{}[{}]", part.data.as_symbol(), disambiguator),
+    });
+    name_builder.build_cgu_name(def_path.krate, components, Some("delta"))
+}
+
 fn mono_item_linkage_and_visibility(
     tcx: TyCtxt<'tcx>,
     mono_item: &MonoItem<'tcx>,
@@ -463,11 +613,10 @@ fn default_visibility(tcx: TyCtxt<'_>, id: DefId, is_generic: bool) -> Visibilit
 
 fn merge_codegen_units<'tcx>(
     tcx: TyCtxt<'tcx>,
-    initial_partitioning: &mut PreInliningPartitioning<'tcx>,
+    codegen_units: &mut Vec<CodegenUnit<'tcx>>,
     target_cgu_count: usize,
 ) {
     assert!(target_cgu_count >= 1);
-    let codegen_units = &mut initial_partitioning.codegen_units;
 
     // Note that at this point in time the `codegen_units` here may not be in a
     // deterministic order (but we know they're deterministically the same set).
@@ -504,6 +653,126 @@ fn merge_codegen_units<'tcx>(
     }
 }
 
+/// Like `merge_codegen_units`, but instead of merging the smallest codegen unit
+/// into the second smallest one, merges it into the codegen unit it shares the
+/// most references with, as long as the result does not grow beyond the size
+/// that an even split into `target_cgu_count` units would have (or the size
+/// of the largest initial unit, if that is bigger). This keeps functions that
+/// call each other in the same codegen unit, where LLVM can inline them and
+/// where they can stay internal.
+fn merge_codegen_units_by_call_graph<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    codegen_units: &mut Vec<CodegenUnit<'tcx>>,
+    target_cgu_count: usize,
+    inlining_map: &InliningMap<'tcx>,
+) {
+    assert!(target_cgu_count >= 1);
+
+    // See `merge_codegen_units` for why we start with a sorted list.
+    codegen_units.sort_by_cached_key(|cgu| cgu.name().as_str());
+
+    let total_size: usize = codegen_units.iter().map(|cgu| cgu.size_estimate()).sum();
+    let largest_size = codegen_units.iter().map(|cgu| cgu.size_estimate()).max().unwrap_or(0);
+    let size_limit = cmp::max((total_size + target_cgu_count - 1) / target_cgu_count, largest_size);
+
+    // The number of references between each pair of codegen units, in both
+    // directions, keyed by codegen unit name.
+    let mut references: FxHashMap<Symbol, FxHashMap<Symbol, usize>> = Default::default();
+    let home_cgus = home_cgus(codegen_units);
+    inlining_map.iter_accesses(|accessor, accessees| {
+        let accessor_cgu = match home_cgus.get(&accessor) {
+            Some(&cgu) => cgu,
+            None => return,
+        };
+        for accessee in accessees {
+            match home_cgus.get(accessee) {
+                Some(&accessee_cgu) if accessee_cgu != accessor_cgu => {
+                    let forward = references.entry(accessor_cgu).or_default();
+                    *forward.entry(accessee_cgu).or_default() += 1;
+                    let backward = references.entry(accessee_cgu).or_default();
+                    *backward.entry(accessor_cgu).or_default() += 1;
+                }
+                _ => {}
+            }
+        }
+    });
+
+    while codegen_units.len() > target_cgu_count {
+        // Sort small cgus to the back
+        codegen_units.sort_by_cached_key(|cgu| cmp::Reverse(cgu.size_estimate()));
+        let mut smallest = codegen_units.pop().unwrap();
+        let smallest_references = references.remove(&smallest.name()).unwrap_or_default();
+
+        let target = codegen_units
+            .iter()
+            .enumerate()
+            .filter(|(_, cgu)| cgu.size_estimate() + smallest.size_estimate() <= size_limit)
+            .map(|(index, cgu)| {
+                let shared = smallest_references.get(&cgu.name()).cloned().unwrap_or(0);
+                (shared, cmp::Reverse(cgu.size_estimate()), index)
+            })
+            .max()
+            .map(|(_, _, index)| index)
+            .unwrap_or(codegen_units.len() - 1);
+        let target = &mut codegen_units[target];
+
+        // Everything that referenced `smallest` now references `target`.
+        for (other, count) in smallest_references {
+            if other == target.name() {
+                continue;
+            }
+            if let Some(other_references) = references.get_mut(&other) {
+                other_references.remove(&smallest.name());
+                *other_references.entry(target.name()).or_default() += count;
+            }
+            *references.entry(target.name()).or_default().entry(other).or_default() += count;
+        }
+        if let Some(target_references) = references.get_mut(&target.name()) {
+            target_references.remove(&smallest.name());
+        }
+
+        target.modify_size_estimate(smallest.size_estimate());
+        for (k, v) in smallest.items_mut().drain() {
+            target.items_mut().insert(k, v);
+        }
+        debug!("CodegenUnit {} merged in to CodegenUnit {}", smallest.name(), target.name());
+    }
+
+    let cgu_name_builder = &mut CodegenUnitNameBuilder::new(tcx);
+    for (index, cgu) in codegen_units.iter_mut().enumerate() {
+        cgu.set_name(numbered_codegen_unit_name(cgu_name_builder, index));
+    }
+}
+
+/// Maps every mono item placed in one of `codegen_units` to the name of that
+/// codegen unit.
+fn home_cgus<'tcx>(codegen_units: &[CodegenUnit<'tcx>]) -> FxHashMap<MonoItem<'tcx>, Symbol> {
+    codegen_units
+        .iter()
+        .flat_map(|cgu| cgu.items().keys().map(move |&item| (item, cgu.name())))
+        .collect()
+}
+
+/// Counts the references from mono items to mono items placed in another
+/// codegen unit, for `-Z cgu-partitioning-stats`.
+fn count_cross_cgu_references<'tcx>(
+    codegen_units: &[CodegenUnit<'tcx>],
+    inlining_map: &InliningMap<'tcx>,
+) -> usize {
+    let home_cgus = home_cgus(codegen_units);
+    let mut count = 0;
+    inlining_map.iter_accesses(|accessor, accessees| {
+        if let Some(accessor_cgu) = home_cgus.get(&accessor) {
+            count += accessees
+                .iter()
+                .filter_map(|accessee| home_cgus.get(accessee))
+                .filter(|&accessee_cgu| accessee_cgu != accessor_cgu)
+                .count();
+        }
+    });
+    count
+}
+
 fn place_inlined_mono_items<'tcx>(
     initial_partitioning: PreInliningPartitioning<'tcx>,
     inlining_map: &InliningMap<'tcx>,
@@ -832,13 +1101,8 @@ where
     }
 }
 
-fn collect_and_partition_mono_items(
-    tcx: TyCtxt<'_>,
-    cnum: CrateNum,
-) -> (Arc<DefIdSet>, Arc<Vec<Arc<CodegenUnit<'_>>>>) {
-    assert_eq!(cnum, LOCAL_CRATE);
-
-    let collection_mode = match tcx.sess.opts.debugging_opts.print_mono_items {
+fn collection_mode(tcx: TyCtxt<'_>) -> MonoItemCollectionMode {
+    match tcx.sess.opts.debugging_opts.print_mono_items {
         Some(ref s) => {
             let mode_string = s.to_lowercase();
             let mode_string = mode_string.trim();
@@ -864,10 +1128,17 @@ fn collect_and_partition_mono_items(
                 MonoItemCollectionMode::Lazy
             }
         }
-    };
+    }
+}
+
+fn collect_and_partition_mono_items(
+    tcx: TyCtxt<'_>,
+    cnum: CrateNum,
+) -> (Arc<DefIdSet>, Arc<Vec<Arc<CodegenUnit<'_>>>>, Option<Arc<FxHashMap<Symbol, Vec<Symbol>>>>) {
+    assert_eq!(cnum, LOCAL_CRATE);
 
     let (items, inlining_map) = time(tcx.sess, "monomorphization collection", || {
-        collector::collect_crate_mono_items(tcx, collection_mode)
+        collector::collect_crate_mono_items(tcx, collection_mode(tcx))
     });
 
     tcx.sess.abort_if_errors();
@@ -877,7 +1148,11 @@ fn collect_and_partition_mono_items(
     let strategy = if tcx.sess.opts.incremental.is_some() {
         PartitioningStrategy::PerModule
     } else {
-        PartitioningStrategy::FixedUnitCount(tcx.sess.codegen_units())
+        let count = tcx.sess.codegen_units();
+        match tcx.sess.opts.debugging_opts.cgu_partitioning {
+            CguPartitioning::Size => PartitioningStrategy::FixedUnitCount(count),
+            CguPartitioning::CallGraph => PartitioningStrategy::CallGraph(count),
+        }
     };
 
     let codegen_units = time(tcx.sess, "codegen unit partitioning", || {
@@ -947,19 +1222,108 @@ fn collect_and_partition_mono_items(
         }
     }
 
-    (Arc::new(mono_items), Arc::new(codegen_units))
+    if tcx.sess.opts.debugging_opts.mono_item_stats {
+        print_mono_item_stats(tcx, items.iter());
+    }
+
+    let call_graph = if tcx.sess.opts.debugging_opts.print_stack_usage
+        || tcx.sess.opts.debugging_opts.stack_limit_warn.is_some()
+    {
+        Some(Arc::new(mono_item_call_graph(tcx, &inlining_map)))
+    } else {
+        None
+    };
+
+    (Arc::new(mono_items), Arc::new(codegen_units), call_graph)
+}
+
+#[derive(RustcEncodable)]
+struct GenericItemStats {
+    name: String,
+    instantiations: usize,
+    size_per_instance: usize,
+    total_size: usize,
+}
+
+/// Implements `-Z mono-item-stats`: prints, for each generic definition, how
+/// many times it was instantiated and how large those instances are estimated
+/// to be (see `MonoItem::size_estimate`), largest total first.
+fn print_mono_item_stats<'a, 'tcx: 'a, I>(tcx: TyCtxt<'tcx>, mono_items: I)
+where
+    I: Iterator<Item = &'a MonoItem<'tcx>>,
+{
+    let mut by_def_id: FxHashMap<DefId, (usize, usize)> = Default::default();
+    for mono_item in mono_items {
+        if let MonoItem::Fn(instance) = *mono_item {
+            if mono_item.is_generic_fn() {
+                let entry = by_def_id.entry(instance.def_id()).or_default();
+                entry.0 += 1;
+                entry.1 += mono_item.size_estimate(tcx);
+            }
+        }
+    }
+
+    let mut stats: Vec<_> = by_def_id
+        .into_iter()
+        .map(|(def_id, (instantiations, total_size))| GenericItemStats {
+            name: tcx.def_path_str(def_id),
+            instantiations,
+            size_per_instance: total_size / instantiations,
+            total_size,
+        })
+        .collect();
+    stats.sort_by(|a, b| b.total_size.cmp(&a.total_size).then_with(|| a.name.cmp(&b.name)));
+
+    if tcx.sess.opts.debugging_opts.mono_item_stats_json {
+        println!("{}", rustc_serialize::json::as_json(&stats));
+        return;
+    }
+
+    println!("MONO_ITEM_STATS {:>10} {:>10} {:>10}  {}", "instances", "size/inst", "total", "item");
+    for stat in &stats {
+        println!(
+            "MONO_ITEM_STATS {:>10} {:>10} {:>10}  {}",
+            stat.instantiations, stat.size_per_instance, stat.total_size, stat.name
+        );
+    }
+}
+
+/// Maps the symbol name of every function collected for codegen to the symbol
+/// names of the functions it references. References are not necessarily calls
+/// (e.g. a function whose address is taken, or a method reachable through a
+/// vtable), so the stack usage computed from this graph is an upper bound.
+fn mono_item_call_graph<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    inlining_map: &InliningMap<'tcx>,
+) -> FxHashMap<Symbol, Vec<Symbol>> {
+    let symbol_name = |mono_item: &MonoItem<'_>| match *mono_item {
+        MonoItem::Fn(instance) => Some(tcx.symbol_name(instance).name),
+        MonoItem::Static(_) | MonoItem::GlobalAsm(_) => None,
+    };
+
+    let mut call_graph: FxHashMap<Symbol, Vec<Symbol>> = Default::default();
+    inlining_map.iter_accesses(|accessor, accessees| {
+        if let Some(caller) = symbol_name(&accessor) {
+            let mut callees: Vec<_> = accessees.iter().filter_map(symbol_name).collect();
+            callees.sort_by_cached_key(|callee| callee.as_str());
+            callees.dedup();
+            call_graph.insert(caller, callees);
+        }
+    });
+
+    call_graph
 }
 
 pub fn provide(providers: &mut Providers<'_>) {
     providers.collect_and_partition_mono_items = collect_and_partition_mono_items;
 
     providers.is_codegened_item = |tcx, def_id| {
-        let (all_mono_items, _) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+        let (all_mono_items, _, _) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
         all_mono_items.contains(&def_id)
     };
 
     providers.codegen_unit = |tcx, name| {
-        let (_, all) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+        let (_, all, _) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
         all.iter()
             .find(|cgu| cgu.name() == name)
             .cloned()
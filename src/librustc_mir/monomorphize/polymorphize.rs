@@ -0,0 +1,131 @@
+//! Polymorphization analysis.
+//!
+//! Functions and closures are frequently generic over parameters that their
+//! body never mentions, e.g., a closure defined inside a generic function
+//! inherits all of the function's parameters, whether it uses them or not.
+//! Every instantiation of such an item is codegened separately even though
+//! the copies are identical. With `-Z polymorphize`, `unused_generic_params`
+//! determines which parameters are never mentioned by the optimized MIR of an
+//! item, and `Instance::polymorphize` replaces the substitutions for those
+//! parameters with the parameters themselves, so that all such instantiations
+//! end up sharing a single mono item.
+
+use rustc::hir::def_id::DefId;
+use rustc::ty::fold::{TypeFoldable, TypeVisitor};
+use rustc::ty::query::Providers;
+use rustc::ty::subst::SubstsRef;
+use rustc::ty::{self, GenericParamDefKind, Ty, TyCtxt};
+
+pub fn provide(providers: &mut Providers<'_>) {
+    providers.unused_generic_params = unused_generic_params;
+}
+
+/// Returns a bitmask in which bit `i` is set if the type or const parameter
+/// with index `i` is not mentioned anywhere in the optimized MIR of `def_id`.
+/// Lifetime parameters are erased before codegen and are never reported,
+/// neither are parameters with an index of 64 or above.
+fn unused_generic_params(tcx: TyCtxt<'_>, def_id: DefId) -> u64 {
+    if !tcx.sess.opts.debugging_opts.polymorphize {
+        return 0;
+    }
+
+    let generics = tcx.generics_of(def_id);
+    if generics.count() == 0 || !tcx.is_mir_available(def_id) {
+        return 0;
+    }
+
+    // The synthetic parameters of a closure or generator (its kind, signature,
+    // upvars, ...) describe the closure itself and are always needed, only the
+    // parameters inherited from the enclosing item are candidates.
+    let mut unused = 0;
+    let mut current = if tcx.is_closure(def_id) { generics.parent } else { Some(def_id) };
+    while let Some(id) = current {
+        let generics = tcx.generics_of(id);
+        for param in &generics.params {
+            let is_type_or_const = match param.kind {
+                GenericParamDefKind::Lifetime => false,
+                GenericParamDefKind::Type { .. } | GenericParamDefKind::Const => true,
+            };
+            if is_type_or_const && param.index < 64 {
+                unused |= 1 << param.index;
+            }
+        }
+        current = generics.parent;
+    }
+    if unused == 0 {
+        return 0;
+    }
+
+    let body = tcx.optimized_mir(def_id);
+    let mut visitor = MarkUsedGenericParams { tcx, def_id, unused };
+    body.visit_with(&mut visitor);
+    debug!("unused_generic_params({:?}) = {:b}", def_id, visitor.unused);
+    visitor.unused
+}
+
+struct MarkUsedGenericParams<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    unused: u64,
+}
+
+impl<'tcx> MarkUsedGenericParams<'tcx> {
+    fn mark_used(&mut self, index: u32) {
+        if index < 64 {
+            self.unused &= !(1 << index);
+        }
+    }
+
+    /// Visits the substitutions of another closure or generator, skipping
+    /// those that the closure itself does not use.
+    fn visit_child_substs(&mut self, def_id: DefId, substs: SubstsRef<'tcx>) -> bool {
+        let unused = self.tcx.unused_generic_params(def_id);
+        substs.iter().enumerate().any(|(i, arg)| {
+            let used = i >= 64 || unused & (1 << i) == 0;
+            used && arg.visit_with(self)
+        })
+    }
+}
+
+impl<'tcx> TypeVisitor<'tcx> for MarkUsedGenericParams<'tcx> {
+    fn visit_ty(&mut self, ty: Ty<'tcx>) -> bool {
+        if !ty.has_param_types() {
+            return false;
+        }
+
+        match ty.kind {
+            ty::Param(param) => {
+                self.mark_used(param.index);
+                false
+            }
+            ty::Closure(def_id, substs) if def_id == self.def_id => {
+                // The closure's own type is the type of its environment, which
+                // only depends on the captured upvars, unless the closure is
+                // `FnOnce` and drops its environment, in which case the drop
+                // glue needs the full type.
+                let substs = substs.as_closure();
+                if substs.kind(def_id, self.tcx) == ty::ClosureKind::FnOnce {
+                    return ty.super_visit_with(self);
+                }
+                substs.upvar_tys(def_id, self.tcx).any(|upvar_ty| upvar_ty.visit_with(self))
+            }
+            ty::Generator(def_id, substs, _) if def_id == self.def_id => {
+                substs.as_generator().upvar_tys(def_id, self.tcx).any(|ty| ty.visit_with(self))
+            }
+            ty::Closure(def_id, substs) | ty::Generator(def_id, substs, _) => {
+                self.visit_child_substs(def_id, substs)
+            }
+            _ => ty.super_visit_with(self),
+        }
+    }
+
+    fn visit_const(&mut self, c: &'tcx ty::Const<'tcx>) -> bool {
+        match c.val {
+            ty::ConstKind::Param(param) => {
+                self.mark_used(param.index);
+                false
+            }
+            _ => c.super_visit_with(self),
+        }
+    }
+}
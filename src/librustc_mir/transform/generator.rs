@@ -172,6 +172,167 @@ impl<'tcx> MutVisitor<'tcx> for PinArgVisitor<'tcx> {
     }
 }
 
+/// Finds the locals whose only assignment moves an upvar into them, like the
+/// bindings of `async fn` parameters, which are moved from the upvars of the
+/// generator into the parameter patterns at the start of the body. Such a
+/// local can live in the storage of its upvar, instead of being saved in the
+/// generator a second time, as long as the upvar isn't used otherwise.
+///
+/// Locals initialized by copying an upvar only qualify if the copy runs once,
+/// i.e. if it is in the entry block and that block isn't part of a loop.
+fn locals_moved_from_upvars<'tcx>(
+    body: ReadOnlyBodyAndCache<'_, 'tcx>,
+) -> FxHashMap<Local, Place<'tcx>> {
+    let mut uses = UpvarMoveVisitor {
+        upvar_uses: FxHashMap::default(),
+        self_arg_used: false,
+        assignments: IndexVec::from_elem(0, &body.local_decls),
+        storage_lives: IndexVec::from_elem(0, &body.local_decls),
+        used_as_index: BitSet::new_empty(body.local_decls.len()),
+    };
+    uses.visit_body(body);
+    if uses.self_arg_used {
+        return FxHashMap::default();
+    }
+
+    let start_in_loop = body
+        .basic_blocks()
+        .iter()
+        .any(|data| data.terminator().successors().any(|&bb| bb == START_BLOCK));
+
+    let mut moved_from_upvars = FxHashMap::default();
+    for (block, data) in body.basic_blocks().iter_enumerated() {
+        for statement in &data.statements {
+            let (place, rvalue) = match statement.kind {
+                StatementKind::Assign(box (ref place, ref rvalue)) => (place, rvalue),
+                _ => continue,
+            };
+            let (upvar, runs_once) = match *rvalue {
+                Rvalue::Use(Operand::Move(ref upvar)) => (upvar, true),
+                Rvalue::Use(Operand::Copy(ref upvar)) => {
+                    (upvar, block == START_BLOCK && !start_in_loop)
+                }
+                _ => continue,
+            };
+            let local = match place.as_local() {
+                Some(local) if local.index() > body.arg_count => local,
+                _ => continue,
+            };
+            if upvar.base != PlaceBase::Local(self_arg()) {
+                continue;
+            }
+            let (field, upvar_ty) = match upvar.projection[..] {
+                [ProjectionElem::Field(field, ty)] => (field, ty),
+                _ => continue,
+            };
+            if runs_once
+                && body.local_decls[local].ty == upvar_ty
+                && uses.upvar_uses.get(&field) == Some(&1)
+                && uses.assignments[local] == 1
+                && uses.storage_lives[local] <= 1
+                && !uses.used_as_index.contains(local)
+            {
+                debug!("local {:?} lives in upvar {:?}", local, upvar);
+                moved_from_upvars.insert(local, upvar.clone());
+            }
+        }
+    }
+    moved_from_upvars
+}
+
+struct UpvarMoveVisitor {
+    // The number of times each upvar is used.
+    upvar_uses: FxHashMap<Field, usize>,
+    // Whether the generator is used other than by accessing its upvars.
+    self_arg_used: bool,
+    // The number of assignments to the whole of each local.
+    assignments: IndexVec<Local, usize>,
+    // The number of `StorageLive` statements of each local.
+    storage_lives: IndexVec<Local, usize>,
+    // The locals used to index a place, which can't be replaced by an upvar.
+    used_as_index: BitSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for UpvarMoveVisitor {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        for elem in place.projection.iter() {
+            if let PlaceElem::Index(local) = *elem {
+                self.used_as_index.insert(local);
+            }
+        }
+
+        match place.base {
+            PlaceBase::Local(local) if local == self_arg() => {
+                // Debuginfo doesn't keep an upvar from being relocated into.
+                if context.is_use() {
+                    match place.projection.first() {
+                        Some(&ProjectionElem::Field(field, _)) => {
+                            *self.upvar_uses.entry(field).or_insert(0) += 1;
+                        }
+                        _ => self.self_arg_used = true,
+                    }
+                }
+            }
+            PlaceBase::Local(local) => {
+                if place.projection.is_empty() && context.is_place_assignment() {
+                    self.assignments[local] += 1;
+                }
+            }
+            PlaceBase::Static(_) => {}
+        }
+    }
+
+    fn visit_local(&mut self, &local: &Local, context: PlaceContext, _: Location) {
+        // Storage markers refer to locals directly rather than to places.
+        if context.is_storage_live_marker() {
+            self.storage_lives[local] += 1;
+        }
+    }
+}
+
+/// Replaces the locals found by `locals_moved_from_upvars` with their upvars,
+/// removing the moves into them and their storage markers.
+struct RelocateUpvarMovesVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    relocated: FxHashMap<Local, Place<'tcx>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for RelocateUpvarMovesVisitor<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_local(&mut self, local: &mut Local, _: PlaceContext, _: Location) {
+        assert_eq!(self.relocated.get(local), None);
+    }
+
+    fn visit_place(&mut self, place: &mut Place<'tcx>, context: PlaceContext, location: Location) {
+        if let PlaceBase::Local(l) = place.base {
+            if let Some(upvar) = self.relocated.get(&l) {
+                replace_base(place, upvar.clone(), self.tcx);
+                return;
+            }
+        }
+        self.super_place(place, context, location);
+    }
+
+    fn visit_basic_block_data(&mut self, block: BasicBlock, data: &mut BasicBlockData<'tcx>) {
+        let relocated = &self.relocated;
+        data.retain_statements(|s| match s.kind {
+            StatementKind::StorageLive(l) | StatementKind::StorageDead(l) => {
+                !relocated.contains_key(&l)
+            }
+            // The move from the upvar, the only assignment to the local.
+            StatementKind::Assign(box (ref place, _)) => place
+                .as_local()
+                .map_or(true, |l| !relocated.contains_key(&l)),
+            _ => true,
+        });
+
+        self.super_basic_block_data(block, data);
+    }
+}
+
 fn replace_base<'tcx>(place: &mut Place<'tcx>, new_base: Place<'tcx>, tcx: TyCtxt<'tcx>) {
     place.base = new_base.base;
 
@@ -431,6 +592,9 @@ struct LivenessInfo {
     /// The set of saved locals live at each suspension point.
     live_locals_at_suspension_points: Vec<BitSet<GeneratorSavedLocal>>,
 
+    /// The source info of the `Yield` terminator at each suspension point.
+    source_info_at_suspension_points: Vec<SourceInfo>,
+
     /// For every saved local, the set of other saved locals that are
     /// storage-live at the same time as this local. We cannot overlap locals in
     /// the layout which have conflicting storage.
@@ -501,6 +665,7 @@ fn locals_live_across_suspend_points(
 
     let mut storage_liveness_map = FxHashMap::default();
     let mut live_locals_at_suspension_points = Vec::new();
+    let mut source_info_at_suspension_points = Vec::new();
 
     for (block, data) in body.basic_blocks().iter_enumerated() {
         if let TerminatorKind::Yield { .. } = data.terminator().kind {
@@ -547,6 +712,7 @@ fn locals_live_across_suspend_points(
             live_locals.union(&live_locals_here);
 
             live_locals_at_suspension_points.push(live_locals_here);
+            source_info_at_suspension_points.push(data.terminator().source_info);
         }
     }
     debug!("live_locals = {:?}", live_locals);
@@ -564,6 +730,7 @@ fn locals_live_across_suspend_points(
     LivenessInfo {
         live_locals,
         live_locals_at_suspension_points,
+        source_info_at_suspension_points,
         storage_conflicts,
         storage_liveness: storage_liveness_map,
     }
@@ -728,6 +895,7 @@ fn compute_layout<'tcx>(
     let LivenessInfo {
         live_locals,
         live_locals_at_suspension_points,
+        source_info_at_suspension_points,
         storage_conflicts,
         storage_liveness,
     } = locals_live_across_suspend_points(tcx, read_only!(body), source, movable);
@@ -759,12 +927,21 @@ fn compute_layout<'tcx>(
         }
     }
 
-    // Gather live local types and their indices.
+    // Gather live local types, their indices and the names of the user
+    // variables among them.
+    let mut var_names = FxHashMap::default();
+    for var in &body.var_debug_info {
+        if let Some(local) = var.place.as_local() {
+            var_names.entry(local).or_insert(var.name);
+        }
+    }
     let mut locals = IndexVec::<GeneratorSavedLocal, _>::new();
     let mut tys = IndexVec::<GeneratorSavedLocal, _>::new();
+    let mut field_names = IndexVec::<GeneratorSavedLocal, _>::new();
     for (idx, local) in live_locals.iter().enumerate() {
         locals.push(local);
         tys.push(body.local_decls[local].ty);
+        field_names.push(var_names.get(&local).copied());
         debug!("generator saved local {:?} => {:?}", GeneratorSavedLocal::from(idx), local);
     }
 
@@ -792,7 +969,22 @@ fn compute_layout<'tcx>(
     debug!("generator variant_fields = {:?}", variant_fields);
     debug!("generator storage_conflicts = {:#?}", storage_conflicts);
 
-    let layout = GeneratorLayout { field_tys: tys, variant_fields, storage_conflicts };
+    // Point the reserved states at the start and end of the generator body, and
+    // every suspend state at the `yield` it was created for.
+    let body_start = source_info(body);
+    let body_end = SourceInfo { span: body.span.shrink_to_hi(), ..body_start };
+    let mut variant_source_info: IndexVec<VariantIdx, SourceInfo> =
+        [body_start, body_end, body_end].iter().copied().collect();
+    variant_source_info.extend(source_info_at_suspension_points);
+    debug!("generator variant_source_info = {:?}", variant_source_info);
+
+    let layout = GeneratorLayout {
+        field_tys: tys,
+        field_names,
+        variant_fields,
+        variant_source_info,
+        storage_conflicts,
+    };
 
     (remap, layout, storage_liveness)
 }
@@ -1141,6 +1333,12 @@ impl<'tcx> MirPass<'tcx> for StateTransform {
         let state_substs = tcx.intern_substs(&[yield_ty.into(), body.return_ty().into()]);
         let ret_ty = tcx.mk_adt(state_adt_ref, state_substs);
 
+        // Let locals that only take over the value of an upvar use its storage.
+        let relocated = locals_moved_from_upvars(read_only!(body));
+        if !relocated.is_empty() {
+            RelocateUpvarMovesVisitor { tcx, relocated }.visit_body(body);
+        }
+
         // We rename RETURN_PLACE which has type mir.return_ty to new_ret_local
         // RETURN_PLACE then is a fresh unused local with type ret_ty.
         let new_ret_local = replace_result_variable(ret_ty, body, tcx);
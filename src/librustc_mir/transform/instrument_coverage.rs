@@ -0,0 +1,141 @@
+//! Injects the code coverage counters of `-Z instrument-coverage`.
+//!
+//! Every basic block that corresponds to some source code gets a counter,
+//! incremented by a call to the `count_code_region` intrinsic that is made
+//! before the block executes. The span of that call is the region of source
+//! code the counter covers, which codegen turns into an entry of the LLVM
+//! coverage map of the function. Regions of different blocks can overlap, in
+//! which case the innermost region determines the count that is reported.
+
+use crate::transform::{MirPass, MirSource};
+use rustc::hir::def_id::DefId;
+use rustc::hir::HirId;
+use rustc::middle::lang_items;
+use rustc::mir::*;
+use rustc::ty::{self, Ty, TyCtxt};
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use std::mem;
+use syntax_pos::Span;
+
+pub struct InstrumentCoverage;
+
+impl<'tcx> MirPass<'tcx> for InstrumentCoverage {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, src: MirSource<'tcx>, body: &mut BodyAndCache<'tcx>) {
+        if !tcx.sess.opts.debugging_opts.instrument_coverage || src.promoted.is_some() {
+            return;
+        }
+
+        let hir_id = tcx.hir().as_local_hir_id(src.def_id()).unwrap();
+        if !tcx.hir().body_owner_kind(hir_id).is_fn_or_closure() {
+            return;
+        }
+
+        // Counters in code that was not written by the user would only
+        // clutter the report.
+        if body.span.from_expansion() {
+            return;
+        }
+
+        let count_code_region_fn =
+            tcx.require_lang_item(lang_items::CountCodeRegionFnLangItem, Some(body.span));
+        let function_source_hash = hash_hir_body(tcx, hir_id);
+
+        let counters: Vec<_> = body
+            .basic_blocks()
+            .iter_enumerated()
+            .filter(|(_, data)| !data.is_cleanup)
+            .filter_map(|(bb, data)| Some((bb, block_region(data, body.span)?)))
+            .collect();
+        let num_counters = counters.len() as u32;
+
+        for (index, (bb, region)) in counters.into_iter().enumerate() {
+            let args = vec![
+                const_int(tcx, function_source_hash as u128, tcx.types.u64, region),
+                const_int(tcx, num_counters as u128, tcx.types.u32, region),
+                const_int(tcx, index as u128, tcx.types.u32, region),
+            ];
+            inject_call(tcx, body, bb, count_code_region_fn, args, region);
+        }
+    }
+}
+
+/// The hash of the HIR of the body of the function, which tells apart the
+/// counters of different versions of its source code.
+fn hash_hir_body(tcx: TyCtxt<'_>, hir_id: HirId) -> u64 {
+    let body = tcx.hir().body(tcx.hir().body_owned_by(hir_id));
+    let mut hcx = tcx.create_stable_hashing_context();
+    let mut hasher = StableHasher::new();
+    hcx.while_hashing_hir_bodies(true, |hcx| {
+        hcx.while_hashing_spans(false, |hcx| body.value.hash_stable(hcx, &mut hasher));
+    });
+    hasher.finish::<Fingerprint>().to_smaller_hash()
+}
+
+/// The region of source code in `body_span` that the statements and the
+/// terminator of a block were lowered from, if any.
+fn block_region(data: &BasicBlockData<'_>, body_span: Span) -> Option<Span> {
+    let terminator_span = match data.terminator().kind {
+        // These carry the span of the whole scope or expression they leave,
+        // which would make the region of the block cover unrelated code.
+        TerminatorKind::Goto { .. }
+        | TerminatorKind::Resume
+        | TerminatorKind::Abort
+        | TerminatorKind::Unreachable
+        | TerminatorKind::GeneratorDrop
+        | TerminatorKind::FalseEdges { .. }
+        | TerminatorKind::FalseUnwind { .. } => None,
+        _ => Some(data.terminator().source_info.span),
+    };
+    data.statements
+        .iter()
+        .map(|statement| statement.source_info.span)
+        .chain(terminator_span)
+        .map(|span| source_span(span, body_span))
+        .filter(|span| !span.is_dummy() && body_span.contains(*span))
+        .fold(None, |region, span| Some(region.map_or(span, |region: Span| region.to(span))))
+}
+
+/// Maps spans from macro expansions and desugarings back to the code that
+/// invoked them in the body of the function.
+fn source_span(mut span: Span, body_span: Span) -> Span {
+    while span.from_expansion() && span.ctxt() != body_span.ctxt() {
+        span = span.ctxt().outer_expn_data().call_site;
+    }
+    span
+}
+
+fn const_int<'tcx>(tcx: TyCtxt<'tcx>, value: u128, ty: Ty<'tcx>, span: Span) -> Operand<'tcx> {
+    Operand::Constant(box Constant {
+        span,
+        user_ty: None,
+        literal: ty::Const::from_bits(tcx, value, ty::ParamEnv::empty().and(ty)),
+    })
+}
+
+/// Makes `bb` call `func` before continuing with its original statements and
+/// terminator, which are moved to a new block.
+fn inject_call<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut BodyAndCache<'tcx>,
+    bb: BasicBlock,
+    func: DefId,
+    args: Vec<Operand<'tcx>>,
+    span: Span,
+) {
+    let temp = body.local_decls.push(LocalDecl::new_temp(tcx.mk_unit(), span));
+    let blocks = body.basic_blocks_mut();
+    let scope = blocks[bb].terminator().source_info.scope;
+    let original = mem::replace(&mut blocks[bb], BasicBlockData::new(None));
+    let next = blocks.push(original);
+    blocks[bb].terminator = Some(Terminator {
+        source_info: SourceInfo { span, scope },
+        kind: TerminatorKind::Call {
+            func: Operand::function_handle(tcx, func, ty::List::empty(), span),
+            args,
+            destination: Some((Place::from(temp), next)),
+            cleanup: None,
+            from_hir_call: false,
+        },
+    });
+}
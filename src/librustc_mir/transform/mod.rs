@@ -27,6 +27,7 @@ pub mod erase_regions;
 pub mod generator;
 pub mod inline;
 pub mod instcombine;
+pub mod instrument_coverage;
 pub mod no_landing_pads;
 pub mod promote_consts;
 pub mod qualify_min_const_fn;
@@ -320,6 +321,9 @@ fn run_optimization_passes<'tcx>(
             &simplify::SimplifyCfg::new("final"),
             &simplify::SimplifyLocals,
             &add_call_guards::CriticalCallEdges,
+            // Counters are injected last, so that they count the blocks that
+            // are actually generated.
+            &instrument_coverage::InstrumentCoverage,
             &dump_mir::Marker("PreCodegen"),
         ],
     );
@@ -2,6 +2,7 @@ use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::sync::Lock;
 use rustc_target::abi::{Align, Size};
 use std::cmp::{self, Ordering};
+use std::fmt;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -20,18 +21,37 @@ pub enum SizeKind {
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FieldInfo {
+    pub kind: FieldKind,
     pub name: String,
     pub offset: u64,
     pub size: u64,
     pub align: u64,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FieldKind {
+    AdtField,
+    Upvar,
+    GeneratorLocal,
+}
+
+impl fmt::Display for FieldKind {
+    fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldKind::AdtField => write!(w, "field"),
+            FieldKind::Upvar => write!(w, "upvar"),
+            FieldKind::GeneratorLocal => write!(w, "local"),
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum DataTypeKind {
     Struct,
     Union,
     Enum,
     Closure,
+    Generator,
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
@@ -113,7 +133,7 @@ impl CodeStats {
 
             let struct_like = match info.kind {
                 DataTypeKind::Struct | DataTypeKind::Closure => true,
-                DataTypeKind::Enum | DataTypeKind::Union => false,
+                DataTypeKind::Enum | DataTypeKind::Union | DataTypeKind::Generator => false,
             };
             for (i, variant_info) in info.variants.iter().enumerate() {
                 let VariantInfo { ref name, kind: _, align: _, size, ref fields } = *variant_info;
@@ -145,7 +165,7 @@ impl CodeStats {
                 fields.sort_by_key(|f| (f.offset, f.size));
 
                 for field in fields.iter() {
-                    let FieldInfo { ref name, offset, size, align } = *field;
+                    let FieldInfo { kind, ref name, offset, size, align } = *field;
 
                     if offset > min_offset {
                         let pad = offset - min_offset;
@@ -155,19 +175,19 @@ impl CodeStats {
                     if offset < min_offset {
                         // If this happens it's probably a union.
                         println!(
-                            "print-type-size {}field `.{}`: {} bytes, \
+                            "print-type-size {}{} `.{}`: {} bytes, \
                                   offset: {} bytes, \
                                   alignment: {} bytes",
-                            indent, name, size, offset, align
+                            indent, kind, name, size, offset, align
                         );
                     } else if info.packed || offset == min_offset {
-                        println!("print-type-size {}field `.{}`: {} bytes", indent, name, size);
+                        println!("print-type-size {}{} `.{}`: {} bytes", indent, kind, name, size);
                     } else {
                         // Include field alignment in output only if it caused padding injection
                         println!(
-                            "print-type-size {}field `.{}`: {} bytes, \
+                            "print-type-size {}{} `.{}`: {} bytes, \
                                   alignment: {} bytes",
-                            indent, name, size, align
+                            indent, kind, name, size, align
                         );
                     }
 
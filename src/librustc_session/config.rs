@@ -41,18 +41,24 @@ pub struct Config {
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Sanitizer {
     Address,
+    KernelAddress,
+    HwAddress,
     Leak,
     Memory,
     Thread,
+    Cfi,
 }
 
 impl fmt::Display for Sanitizer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Sanitizer::Address => "address".fmt(f),
+            Sanitizer::KernelAddress => "kernel-address".fmt(f),
+            Sanitizer::HwAddress => "hwaddress".fmt(f),
             Sanitizer::Leak => "leak".fmt(f),
             Sanitizer::Memory => "memory".fmt(f),
             Sanitizer::Thread => "thread".fmt(f),
+            Sanitizer::Cfi => "cfi".fmt(f),
         }
     }
 }
@@ -62,9 +68,12 @@ impl FromStr for Sanitizer {
     fn from_str(s: &str) -> Result<Sanitizer, ()> {
         match s {
             "address" => Ok(Sanitizer::Address),
+            "kernel-address" => Ok(Sanitizer::KernelAddress),
+            "hwaddress" => Ok(Sanitizer::HwAddress),
             "leak" => Ok(Sanitizer::Leak),
             "memory" => Ok(Sanitizer::Memory),
             "thread" => Ok(Sanitizer::Thread),
+            "cfi" => Ok(Sanitizer::Cfi),
             _ => Err(()),
         }
     }
@@ -133,6 +142,30 @@ impl LinkerPluginLto {
     }
 }
 
+/// The `-Z split-debuginfo` mode, which decides where the DWARF of objects
+/// goes on Linux.
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+pub enum SplitDebuginfo {
+    /// The debuginfo is linked into the output along with the code.
+    Off,
+    /// The debuginfo goes into a `.dwo` file next to every object, and the
+    /// `.dwo` files are packaged into a `.dwp` file next to the linked output.
+    Packed,
+    /// The debuginfo goes into a `.dwo` file next to every object, which the
+    /// linked output refers to.
+    Unpacked,
+}
+
+/// The `-Z cgu-partitioning` strategy, which decides how codegen units are
+/// merged in non-incremental builds.
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+pub enum CguPartitioning {
+    /// Merge the smallest codegen units until few enough are left.
+    Size,
+    /// Merge the codegen units that reference each other the most.
+    CallGraph,
+}
+
 #[derive(Clone, PartialEq, Hash)]
 pub enum SwitchWithOptPath {
     Enabled(Option<PathBuf>),
@@ -173,6 +206,7 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    CHeader,
 }
 
 impl_stable_hash_via_hash!(OutputType);
@@ -180,7 +214,10 @@ impl_stable_hash_via_hash!(OutputType);
 impl OutputType {
     fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
         match *self {
-            OutputType::Exe | OutputType::DepInfo | OutputType::Metadata => true,
+            OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::Metadata
+            | OutputType::CHeader => true,
             OutputType::Bitcode
             | OutputType::Assembly
             | OutputType::LlvmAssembly
@@ -199,6 +236,7 @@ impl OutputType {
             OutputType::Metadata => "metadata",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
+            OutputType::CHeader => "c-header",
         }
     }
 
@@ -212,13 +250,14 @@ impl OutputType {
             "metadata" => OutputType::Metadata,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
+            "c-header" => OutputType::CHeader,
             _ => return None,
         })
     }
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::Assembly.shorthand(),
             OutputType::LlvmAssembly.shorthand(),
@@ -227,6 +266,7 @@ impl OutputType {
             OutputType::Metadata.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
+            OutputType::CHeader.shorthand(),
         )
     }
 
@@ -239,6 +279,7 @@ impl OutputType {
             OutputType::Object => "o",
             OutputType::Metadata => "rmeta",
             OutputType::DepInfo => "d",
+            OutputType::CHeader => "h",
             OutputType::Exe => "",
         }
     }
@@ -307,7 +348,7 @@ impl OutputTypes {
             | OutputType::Mir
             | OutputType::Object
             | OutputType::Exe => true,
-            OutputType::Metadata | OutputType::DepInfo => false,
+            OutputType::Metadata | OutputType::DepInfo | OutputType::CHeader => false,
         })
     }
 }
@@ -456,6 +497,7 @@ pub struct OutputFilenames {
 impl_stable_hash_via_hash!(OutputFilenames);
 
 pub const RUST_CGU_EXT: &str = "rcgu";
+pub const DWARF_OBJECT_EXT: &str = "dwo";
 
 impl OutputFilenames {
     pub fn path(&self, flavor: OutputType) -> PathBuf {
@@ -474,6 +516,12 @@ impl OutputFilenames {
         self.temp_path_ext(extension, codegen_unit_name)
     }
 
+    /// Gets the path of the split DWARF object of a codegen unit, which is
+    /// written along with its object file.
+    pub fn temp_path_dwo(&self, codegen_unit_name: Option<&str>) -> PathBuf {
+        self.temp_path_ext(DWARF_OBJECT_EXT, codegen_unit_name)
+    }
+
     /// Like temp_path, but also supports things where there is no corresponding
     /// OutputType, like noopt-bitcode or lto-bitcode.
     pub fn temp_path_ext(&self, ext: &str, codegen_unit_name: Option<&str>) -> PathBuf {
@@ -869,7 +917,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "emit",
             "Comma separated list of types of output for \
              the compiler to emit",
-            "[asm|llvm-bc|llvm-ir|obj|metadata|link|dep-info|mir]",
+            "[asm|llvm-bc|llvm-ir|obj|metadata|link|dep-info|mir|c-header]",
         ),
         opt::multi_s(
             "",
@@ -1954,8 +2002,8 @@ impl PpMode {
 /// how the hash should be calculated when adding a new command-line argument.
 crate mod dep_tracking {
     use super::{
-        CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli, OptLevel, OutputTypes,
-        Passes, Sanitizer, SwitchWithOptPath, SymbolManglingVersion,
+        CguPartitioning, CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli, OptLevel,
+        OutputTypes, Passes, Sanitizer, SplitDebuginfo, SwitchWithOptPath, SymbolManglingVersion,
     };
     use crate::lint;
     use crate::utils::NativeLibraryKind;
@@ -2032,6 +2080,8 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(LinkerPluginLto);
     impl_dep_tracking_hash_via_hash!(SwitchWithOptPath);
     impl_dep_tracking_hash_via_hash!(SymbolManglingVersion);
+    impl_dep_tracking_hash_via_hash!(SplitDebuginfo);
+    impl_dep_tracking_hash_via_hash!(CguPartitioning);
 
     impl_dep_tracking_hash_for_sortable_vec_of!(String);
     impl_dep_tracking_hash_for_sortable_vec_of!(PathBuf);
@@ -259,7 +259,8 @@ macro_rules! options {
         pub const parse_relro_level: Option<&str> =
             Some("one of: `full`, `partial`, or `off`");
         pub const parse_sanitizer: Option<&str> =
-            Some("one of: `address`, `leak`, `memory` or `thread`");
+            Some("one of: `address`, `cfi`, `hwaddress`, `kernel-address`, `leak`, \
+                  `memory` or `thread`");
         pub const parse_sanitizer_list: Option<&str> =
             Some("comma separated list of sanitizers");
         pub const parse_sanitizer_memory_track_origins: Option<&str> = None;
@@ -283,12 +284,16 @@ macro_rules! options {
             Some("one of: `disabled`, `trampolines`, or `aliases`");
         pub const parse_symbol_mangling_version: Option<&str> =
             Some("either `legacy` or `v0` (RFC 2603)");
+        pub const parse_split_debuginfo: Option<&str> =
+            Some("one of: `off`, `packed`, or `unpacked`");
+        pub const parse_cgu_partitioning: Option<&str> =
+            Some("either `size` or `callgraph`");
     }
 
     #[allow(dead_code)]
     mod $mod_set {
-        use super::{$struct_name, Passes, Sanitizer, LtoCli, LinkerPluginLto, SwitchWithOptPath,
-            SymbolManglingVersion};
+        use super::{$struct_name, Passes, Sanitizer, LtoCli, LinkerPluginLto,
+            SwitchWithOptPath, SymbolManglingVersion, SplitDebuginfo, CguPartitioning};
         use rustc_target::spec::{LinkerFlavor, MergeFunctions, PanicStrategy, RelroLevel};
         use std::path::PathBuf;
         use std::str::FromStr;
@@ -609,6 +614,25 @@ macro_rules! options {
             };
             true
         }
+
+        fn parse_split_debuginfo(slot: &mut SplitDebuginfo, v: Option<&str>) -> bool {
+            *slot = match v {
+                Some("off") => SplitDebuginfo::Off,
+                Some("packed") => SplitDebuginfo::Packed,
+                Some("unpacked") => SplitDebuginfo::Unpacked,
+                _ => return false,
+            };
+            true
+        }
+
+        fn parse_cgu_partitioning(slot: &mut CguPartitioning, v: Option<&str>) -> bool {
+            *slot = match v {
+                Some("size") => CguPartitioning::Size,
+                Some("callgraph") => CguPartitioning::CallGraph,
+                _ => return false,
+            };
+            true
+        }
     }
 ) }
 
@@ -856,6 +880,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "Enable recovery for selected sanitizers"),
     sanitizer_memory_track_origins: usize = (0, parse_sanitizer_memory_track_origins, [TRACKED],
         "Enable origins tracking in MemorySanitizer"),
+    cfi_type_metadata: bool = (false, parse_bool, [TRACKED],
+        "tag functions and vtables with the type identifiers `-Z sanitizer=cfi` checks \
+         indirect calls against, without checking the indirect calls of this crate"),
     fuel: Option<(String, u64)> = (None, parse_optimization_fuel, [TRACKED],
         "set the optimization fuel quota for a crate"),
     print_fuel: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -929,6 +956,12 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "specifies which kinds of events get recorded by the self profiler"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emits a section containing stack size metadata"),
+    split_debuginfo: SplitDebuginfo = (SplitDebuginfo::Off, parse_split_debuginfo, [TRACKED],
+        "write the debuginfo of objects to `.dwo` files, packaged into a `.dwp` file \
+         with `packed` (Linux only)"),
+    cross_lang_lto: bool = (false, parse_bool, [TRACKED],
+        "enable linker-based LTO with the `LLVMgold` linker plugin shipped in the sysroot, \
+         if it is built from the LLVM of rustc and the target supports it"),
     plt: Option<bool> = (None, parse_opt_bool, [TRACKED],
           "whether to use the PLT when calling into shared libraries;
           only has effect for PIC code on systems with ELF binaries
@@ -946,4 +979,44 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
     insert_sideeffect: bool = (false, parse_bool, [TRACKED],
         "fix undefined behavior when a thread doesn't eventually make progress \
          (such as entering an empty infinite loop) by inserting llvm.sideeffect"),
+    dump_trait_solver: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print a trace of trait selection for each obligation whose predicate contains \
+         the given string (`all` traces every obligation)"),
+    dump_trait_solver_json: bool = (false, parse_bool, [UNTRACKED],
+        "print the trace requested by `-Z dump-trait-solver` as JSON"),
+    polymorphize: bool = (false, parse_bool, [TRACKED],
+        "share a single copy of a function between instantiations that only differ \
+         in generic parameters the function does not use"),
+    mono_item_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print the number of instantiations and the estimated size of each generic item \
+         collected for codegen"),
+    mono_item_stats_json: bool = (false, parse_bool, [UNTRACKED],
+        "print the statistics requested by `-Z mono-item-stats` as JSON"),
+    cgu_partitioning: CguPartitioning = (CguPartitioning::Size,
+        parse_cgu_partitioning, [TRACKED],
+        "how to merge codegen units in non-incremental builds: `size` (default) or \
+         `callgraph`, which keeps items that reference each other together; incremental \
+         builds always partition per module"),
+    cgu_partitioning_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print the number of references between codegen units after partitioning"),
+    incremental_delta_cgus: bool = (false, parse_bool, [TRACKED],
+        "in incremental builds, move functions that changed since the previous session \
+         into codegen units of their own, so that the rest of their module can be reused"),
+    print_stack_usage: bool = (false, parse_bool, [TRACKED],
+        "print the stack frame size of each function and the worst-case stack usage of \
+         the calls made from it (requires an ELF target)"),
+    stack_limit_warn: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "warn about functions whose worst-case stack usage exceeds the given number of bytes \
+         (requires an ELF target)"),
+    staticlib_dce: bool = (false, parse_bool, [TRACKED],
+        "when only building a static library, run LTO over the whole crate graph so that \
+         only code reachable from the exported C API is kept"),
+    instrument_coverage: bool = (false, parse_bool, [TRACKED],
+        "instrument the generated code with counters for source-based code coverage reports \
+         (written to `default.profraw` at runtime, read with `llvm-profdata` and `llvm-cov`)"),
+    link_self_contained: bool = (false, parse_bool, [UNTRACKED],
+        "link static executables with the `rust-lld`, CRT objects and C library shipped with \
+         rustc instead of the system C toolchain (only for targets shipping them, e.g. musl)"),
+    linker_log: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "save the linker command line and the contents of its response file to the given file"),
 }
@@ -1,11 +1,12 @@
 use crate::code_stats::CodeStats;
-pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+pub use crate::code_stats::{DataTypeKind, FieldInfo, FieldKind, SizeKind, VariantInfo};
 
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 
-use crate::config::{self, OutputType, PrintRequest, Sanitizer, SwitchWithOptPath};
+use crate::config::{self, CguPartitioning, LinkerPluginLto, OutputType, PrintRequest};
+use crate::config::{DebugInfo, Sanitizer, SplitDebuginfo, SwitchWithOptPath};
 use crate::filesearch;
 use crate::lint;
 use crate::search_paths::{PathKind, SearchPath};
@@ -31,15 +32,16 @@ use syntax_pos::{MultiSpan, Span};
 use rustc_data_structures::flock;
 use rustc_data_structures::jobserver::{self, Client};
 use rustc_data_structures::profiling::{SelfProfiler, SelfProfilerRef};
-use rustc_target::spec::{PanicStrategy, RelroLevel, Target, TargetTriple};
+use rustc_target::spec::{LinkerFlavor, PanicStrategy, RelroLevel, Target, TargetTriple};
 
 use std;
 use std::cell::{self, RefCell};
 use std::env;
 use std::fmt;
+use std::fs;
 use std::io::Write;
 use std::num::NonZeroU32;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -438,6 +440,27 @@ impl Session {
         self.features.set(features);
     }
 
+    /// Warns if `-Z staticlib-dce` is given for a crate that isn't only built
+    /// as a static library, as fat LTO is then not enabled for it.
+    pub fn check_staticlib_dce(&self, crate_types: &[config::CrateType]) {
+        if self.opts.debugging_opts.staticlib_dce
+            && crate_types.iter().any(|&ty| ty != config::CrateType::Staticlib)
+        {
+            self.warn(
+                "`-Z staticlib-dce` has no effect unless the crate is only built as a \
+                 static library",
+            );
+        }
+    }
+
+    /// Whether functions and vtables are tagged with the type identifiers that
+    /// the control flow integrity checks of indirect calls test, which crates
+    /// linked into a program built with `-Z sanitizer=cfi` must be.
+    pub fn emits_cfi_type_metadata(&self) -> bool {
+        self.opts.debugging_opts.sanitizer == Some(Sanitizer::Cfi)
+            || self.opts.debugging_opts.cfi_type_metadata
+    }
+
     /// Calculates the flavor of LTO to use for this compilation.
     pub fn lto(&self) -> config::Lto {
         // If our target has codegen requirements ignore the command line
@@ -470,6 +493,15 @@ impl Session {
             }
         }
 
+        // With `-Z staticlib-dce`, static libraries are built with LTO over the
+        // whole crate graph, which internalizes everything but the exported C
+        // API and lets LLVM remove all code that isn't reachable from it.
+        if self.opts.debugging_opts.staticlib_dce
+            && self.crate_types.borrow().iter().all(|&ty| ty == config::CrateType::Staticlib)
+        {
+            return config::Lto::Fat;
+        }
+
         // Ok at this point the target doesn't require anything and the user
         // hasn't asked for anything. Our next decision is whether or not
         // we enable "auto" ThinLTO where we use multiple codegen units and
@@ -511,6 +543,20 @@ impl Session {
     pub fn panic_strategy(&self) -> PanicStrategy {
         self.opts.cg.panic.unwrap_or(self.target.target.options.panic_strategy)
     }
+
+    /// Returns how the debuginfo is split out of the objects. It is never
+    /// split without debuginfo, or if LLVM doesn't write the objects itself.
+    pub fn split_debuginfo(&self) -> SplitDebuginfo {
+        let obj_is_bitcode =
+            self.target.target.options.obj_is_bitcode || self.opts.cg.linker_plugin_lto.enabled();
+        let no_integrated_as =
+            self.opts.cg.no_integrated_as || self.target.target.options.no_integrated_as;
+        if self.opts.debuginfo == DebugInfo::None || obj_is_bitcode || no_integrated_as {
+            SplitDebuginfo::Off
+        } else {
+            self.opts.debugging_opts.split_debuginfo
+        }
+    }
     pub fn fewer_names(&self) -> bool {
         let more_names = self.opts.output_types.contains_key(&OutputType::LlvmAssembly)
             || self.opts.output_types.contains_key(&OutputType::Bitcode);
@@ -518,6 +564,8 @@ impl Session {
         // Address sanitizer and memory sanitizer use alloca name when reporting an issue.
         let more_names = match self.opts.debugging_opts.sanitizer {
             Some(Sanitizer::Address) => true,
+            Some(Sanitizer::KernelAddress) => true,
+            Some(Sanitizer::HwAddress) => true,
             Some(Sanitizer::Memory) => true,
             _ => more_names,
         };
@@ -972,7 +1020,7 @@ pub fn build_session_with_source_map(
 }
 
 fn build_session_(
-    sopts: config::Options,
+    mut sopts: config::Options,
     local_crate_source_file: Option<PathBuf>,
     span_diagnostic: rustc_errors::Handler,
     source_map: Lrc<source_map::SourceMap>,
@@ -1011,6 +1059,8 @@ fn build_session_(
         None => filesearch::get_or_default_sysroot(),
     };
 
+    resolve_cross_lang_lto(&mut sopts, &target_cfg, &sysroot, &parse_sess.span_diagnostic);
+
     let host_triple = config::host_triple();
     let target_triple = sopts.target_triple.triple();
     let host_tlib_path = SearchPath::from_sysroot_and_triple(&sysroot, host_triple);
@@ -1087,6 +1137,102 @@ fn build_session_(
     sess
 }
 
+/// The prefix and suffix of the name of the LLVM linker plugin for the gold
+/// and BFD linkers, which is named after the version of LLVM it is built from.
+const LINKER_PLUGIN_PREFIX: &str = "LLVMgold-";
+const LINKER_PLUGIN_SUFFIX: &str = ".so";
+
+/// Resolves `-Z cross-lang-lto` into `-C linker-plugin-lto` with the linker
+/// plugin shipped next to the host libraries of the sysroot. The plugin only
+/// understands the bitcode of the LLVM it was built from, so it is named
+/// after the version of that LLVM, e.g. `LLVMgold-9.0.so`, and only a plugin
+/// for the LLVM of rustc is used. If there is none, or the target does not
+/// link through a linker that can load it, this warns and builds without
+/// cross-language LTO instead. Builds that don't codegen, like `cargo check`,
+/// have nothing to warn about.
+fn resolve_cross_lang_lto(
+    sopts: &mut config::Options,
+    target_cfg: &config::Config,
+    sysroot: &Path,
+    handler: &rustc_errors::Handler,
+) {
+    if !sopts.debugging_opts.cross_lang_lto {
+        return;
+    }
+
+    if sopts.cg.linker_plugin_lto.enabled() {
+        // The user already chose a linker plugin, don't second-guess them.
+        return;
+    }
+
+    // The plugin is only used by the linker, so there's nothing to warn about
+    // when nothing is linked.
+    if !sopts.output_types.contains_key(&OutputType::Exe) || sopts.debugging_opts.no_codegen {
+        return;
+    }
+
+    let target = &target_cfg.target;
+    let linker_flavor = sopts.cg.linker_flavor.unwrap_or(target.linker_flavor);
+    if target.target_os != "linux" || linker_flavor != LinkerFlavor::Gcc {
+        handler.warn(&format!(
+            "`-Z cross-lang-lto` is not supported for target `{}` with linker flavor `{}`, \
+             building without cross-language LTO",
+            sopts.target_triple,
+            linker_flavor.desc()
+        ));
+        return;
+    }
+
+    // Set by the build system when rustc is built with LLVM.
+    let llvm_version = match option_env!("CFG_LLVM_VERSION") {
+        Some(llvm_version) => llvm_version,
+        None => {
+            handler.warn(
+                "`-Z cross-lang-lto` is not supported by this rustc, which was built without \
+                 LLVM, building without cross-language LTO",
+            );
+            return;
+        }
+    };
+
+    let libdir = filesearch::make_target_lib_path(sysroot, config::host_triple());
+    let plugin_name = format!("{}{}{}", LINKER_PLUGIN_PREFIX, llvm_version, LINKER_PLUGIN_SUFFIX);
+    let plugin = libdir.join(&plugin_name);
+    if plugin.exists() {
+        sopts.cg.linker_plugin_lto = LinkerPluginLto::LinkerPlugin(plugin);
+        return;
+    }
+
+    // Point out a plugin for another LLVM, which can't be used instead.
+    let other_plugin = fs::read_dir(&libdir).into_iter().flatten().find_map(|entry| {
+        let name = entry.ok()?.file_name().into_string().ok()?;
+        if name.starts_with(LINKER_PLUGIN_PREFIX) && name.ends_with(LINKER_PLUGIN_SUFFIX) {
+            Some(name)
+        } else {
+            None
+        }
+    });
+    match other_plugin {
+        Some(name) => {
+            let version =
+                &name[LINKER_PLUGIN_PREFIX.len()..name.len() - LINKER_PLUGIN_SUFFIX.len()];
+            handler.warn(&format!(
+                "`-Z cross-lang-lto` found the linker plugin `{}`, which is built from LLVM {} \
+                 while rustc uses LLVM {}, building without cross-language LTO",
+                name, version, llvm_version
+            ));
+        }
+        None => {
+            handler.warn(&format!(
+                "`-Z cross-lang-lto` could not find the linker plugin `{}` in `{}`, \
+                 building without cross-language LTO",
+                plugin_name,
+                libdir.display()
+            ));
+        }
+    }
+}
+
 // If it is useful to have a Session available already for validating a
 // commandline argument, you can do so here.
 fn validate_commandline_args_with_session_available(sess: &Session) {
@@ -1135,6 +1281,80 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
                   See https://github.com/rust-lang/rust/issues/61002 for details.",
         );
     }
+
+    // The control flow integrity checks can only be resolved once the whole
+    // program is visible, which is the case with fat LTO, or when the linker
+    // performs LTO on the bitcode we emit.
+    if sess.opts.debugging_opts.sanitizer == Some(Sanitizer::Cfi) {
+        let fat_lto = match sess.opts.cg.lto {
+            config::LtoCli::Yes | config::LtoCli::Fat | config::LtoCli::NoParam => true,
+            _ => sess.target.target.options.requires_lto,
+        };
+        if !fat_lto && !sess.opts.cg.linker_plugin_lto.enabled() {
+            sess.err("`-Z sanitizer=cfi` requires `-C lto` or `-C linker-plugin-lto`");
+        }
+    }
+
+    // `-Z staticlib-dce` relies on fat LTO to drop the code that isn't
+    // reachable from the exported C API, so an explicit request for another
+    // kind of LTO would silently disable it.
+    if sess.opts.debugging_opts.staticlib_dce {
+        let lto = match sess.opts.cg.lto {
+            config::LtoCli::No => Some("no"),
+            config::LtoCli::Thin => Some("thin"),
+            _ => None,
+        };
+        if let Some(lto) = lto {
+            sess.err(&format!(
+                "`-Z staticlib-dce` requires fat LTO and cannot be combined with `-C lto={}`",
+                lto
+            ));
+        }
+    }
+
+    // Crate types given through `#![crate_type]` are checked once the crate
+    // attributes are parsed.
+    if !sess.opts.crate_types.is_empty() {
+        sess.check_staticlib_dce(&sess.opts.crate_types);
+    }
+
+    // Incremental builds always partition codegen units per module, so that
+    // editing a function only invalidates the codegen unit of its module.
+    if sess.opts.debugging_opts.cgu_partitioning == CguPartitioning::CallGraph
+        && sess.opts.incremental.is_some()
+    {
+        sess.warn(
+            "`-Z cgu-partitioning=callgraph` has no effect in incremental builds, which \
+             partition codegen units per module",
+        );
+    }
+
+    // Split debuginfo relies on DWARF fission, which only the toolchains of
+    // Linux are known to support.
+    if sess.opts.debugging_opts.split_debuginfo != SplitDebuginfo::Off
+        && sess.target.target.target_os != "linux"
+    {
+        sess.err("`-Z split-debuginfo` is only supported on Linux targets");
+    }
+
+    // Self-contained linking picks the linker itself and only knows how to
+    // produce statically linked executables.
+    if sess.opts.debugging_opts.link_self_contained {
+        if !sess.target.target.options.crt_static_self_contained {
+            sess.err(&format!(
+                "`-Z link-self-contained` is not supported for the `{}` target",
+                sess.opts.target_triple.triple()
+            ));
+        } else if !sess.crt_static() {
+            sess.err("`-Z link-self-contained` requires `-C target-feature=+crt-static`");
+        }
+        if sess.opts.cg.linker.is_some() || sess.opts.cg.linker_flavor.is_some() {
+            sess.err(
+                "`-Z link-self-contained` cannot be combined with `-C linker` or \
+                 `-C linker-flavor`",
+            );
+        }
+    }
 }
 
 /// Hash value constructed out of all the `-C metadata` arguments passed to the
@@ -1,4 +1,4 @@
-use crate::spec::{LinkArgs, LinkerFlavor, RelroLevel, TargetOptions};
+use crate::spec::{LinkArgs, LinkerFlavor, LldFlavor, RelroLevel, TargetOptions};
 use std::default::Default;
 
 pub fn opts() -> TargetOptions {
@@ -18,6 +18,11 @@ pub fn opts() -> TargetOptions {
             "-Wl,-z,noexecstack".to_string(),
         ],
     );
+    // The same, for when `rust-lld` is invoked directly as a GNU-style linker.
+    args.insert(
+        LinkerFlavor::Lld(LldFlavor::Ld),
+        vec!["--as-needed".to_string(), "-z".to_string(), "noexecstack".to_string()],
+    );
 
     TargetOptions {
         dynamic_linking: true,
@@ -1,4 +1,4 @@
-use crate::spec::{LinkerFlavor, TargetOptions};
+use crate::spec::{LinkerFlavor, LldFlavor, TargetOptions};
 
 pub fn opts() -> TargetOptions {
     let mut base = super::linux_base::opts();
@@ -13,6 +13,10 @@ pub fn opts() -> TargetOptions {
     // when unwinding to locate the unwinding information. I'm not sure why this
     // argument is *not* necessary for normal builds, but it can't hurt!
     base.pre_link_args.get_mut(&LinkerFlavor::Gcc).unwrap().push("-Wl,--eh-frame-hdr".to_string());
+    base.pre_link_args
+        .get_mut(&LinkerFlavor::Lld(LldFlavor::Ld))
+        .unwrap()
+        .push("--eh-frame-hdr".to_string());
 
     // When generating a statically linked executable there's generally some
     // small setup needed which is listed in these files. These are provided by
@@ -24,6 +28,9 @@ pub fn opts() -> TargetOptions {
     base.pre_link_objects_exe_crt.push("crt1.o".to_string());
     base.pre_link_objects_exe_crt.push("crti.o".to_string());
     base.post_link_objects_crt.push("crtn.o".to_string());
+    // Together with the static `libc.a` bundled into the `libc` crate, that is
+    // everything `rust-lld` needs to link a static executable on its own.
+    base.crt_static_self_contained = true;
 
     // These targets statically link libc by default
     base.crt_static_default = true;
@@ -733,6 +733,10 @@ pub struct TargetOptions {
     pub crt_static_default: bool,
     /// Whether or not crt-static is respected by the compiler (or is a no-op).
     pub crt_static_respected: bool,
+    /// Whether or not the target libraries shipped with rustc include the CRT
+    /// objects and C library needed to link a statically linked executable
+    /// with `rust-lld` alone, see `-Z link-self-contained`.
+    pub crt_static_self_contained: bool,
 
     /// Whether or not stack probes (__rust_probestack) are enabled
     pub stack_probes: bool,
@@ -874,6 +878,7 @@ impl Default for TargetOptions {
             crt_static_allows_dylibs: false,
             crt_static_default: false,
             crt_static_respected: false,
+            crt_static_self_contained: false,
             stack_probes: false,
             min_global_align: None,
             default_codegen_units: None,
@@ -1187,6 +1192,7 @@ impl Target {
         key!(crt_static_allows_dylibs, bool);
         key!(crt_static_default, bool);
         key!(crt_static_respected, bool);
+        key!(crt_static_self_contained, bool);
         key!(stack_probes, bool);
         key!(min_global_align, Option<u64>);
         key!(default_codegen_units, Option<u64>);
@@ -1414,6 +1420,7 @@ impl ToJson for Target {
         target_option_val!(crt_static_allows_dylibs);
         target_option_val!(crt_static_default);
         target_option_val!(crt_static_respected);
+        target_option_val!(crt_static_self_contained);
         target_option_val!(stack_probes);
         target_option_val!(min_global_align);
         target_option_val!(default_codegen_units);
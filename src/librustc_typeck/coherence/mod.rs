@@ -9,6 +9,7 @@ use crate::hir::def_id::{DefId, LOCAL_CRATE};
 use crate::hir::HirId;
 use rustc::traits;
 use rustc::ty::query::Providers;
+use rustc::ty::trait_def::TraitSpecializationKind;
 use rustc::ty::{self, TyCtxt, TypeFoldable};
 use rustc::util::common::time;
 
@@ -74,6 +75,20 @@ fn enforce_trait_manually_implementable(tcx: TyCtxt<'_>, impl_def_id: DefId, tra
         return;
     }
 
+    // Impls of `#[rustc_specialization_trait]` traits can be specialized on,
+    // so they have to be always applicable, which is only checked with
+    // `min_specialization`.
+    if tcx.trait_def(trait_def_id).specialization_kind == TraitSpecializationKind::AlwaysApplicable
+        && !tcx.features().specialization
+        && !tcx.features().min_specialization
+    {
+        tcx.sess
+            .struct_span_err(span, "implementing `rustc_specialization_trait` traits is unstable")
+            .help("add `#![feature(min_specialization)]` to the crate attributes to enable")
+            .emit();
+        return;
+    }
+
     if tcx.features().unboxed_closures {
         // the feature gate allows all Fn traits
         return;
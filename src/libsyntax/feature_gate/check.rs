@@ -689,7 +689,16 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
 
     fn visit_assoc_item(&mut self, ii: &'a ast::AssocItem) {
         if ii.defaultness == ast::Defaultness::Default {
-            gate_feature_post!(&self, specialization, ii.span, "specialization is unstable");
+            // `min_specialization` only allows specializing methods.
+            let is_fn = if let ast::AssocItemKind::Fn(..) = ii.kind { true } else { false };
+            gate_feature_fn!(
+                &self,
+                |x: &Features| x.specialization || (is_fn && x.min_specialization),
+                ii.span,
+                sym::specialization,
+                "specialization is unstable",
+                GateStrength::Hard
+            );
         }
 
         match ii.kind {
@@ -122,6 +122,7 @@ symbols! {
         abi_x86_interrupt,
         aborts,
         add_with_overflow,
+        address,
         advanced_slice_patterns,
         adx_target_feature,
         alias,
@@ -227,6 +228,7 @@ symbols! {
         copy_closures,
         core,
         core_intrinsics,
+        count_code_region,
         crate_id,
         crate_in_paths,
         crate_local,
@@ -243,6 +245,7 @@ symbols! {
         custom_inner_attributes,
         custom_test_frameworks,
         c_variadic,
+        debugger_visualizer,
         debug_trait,
         declare_lint_pass,
         decl_macro,
@@ -335,6 +338,7 @@ symbols! {
         Future,
         FxHashSet,
         FxHashMap,
+        gdb_script,
         gen_future,
         generators,
         generic_associated_types,
@@ -348,6 +352,7 @@ symbols! {
         HashMap,
         hexagon_target_feature,
         hidden,
+        hwaddress,
         homogeneous_aggregate,
         html_favicon_url,
         html_logo_url,
@@ -443,11 +448,13 @@ symbols! {
         mem_uninitialized,
         mem_zeroed,
         member_constraints,
+        memory,
         message,
         meta,
         min_align_of,
         min_const_fn,
         min_const_unsafe_fn,
+        min_specialization,
         mips_target_feature,
         mmx_target_feature,
         module,
@@ -481,6 +488,7 @@ symbols! {
         no_link,
         no_main,
         no_mangle,
+        no_sanitize,
         non_ascii_idents,
         None,
         non_exhaustive,
@@ -645,6 +653,7 @@ symbols! {
         rustc_proc_macro_decls,
         rustc_promotable,
         rustc_regions,
+        rustc_specialization_trait,
         rustc_stable,
         rustc_std_internal_symbol,
         rustc_symbol_name,
@@ -652,6 +661,7 @@ symbols! {
         rustc_reservation_impl,
         rustc_test_marker,
         rustc_then_this_would_need,
+        rustc_unsafe_specialization_marker,
         rustc_variance,
         rustfmt,
         rust_eh_personality,
@@ -719,6 +729,7 @@ symbols! {
         test_removed_feature,
         test_runner,
         then_with,
+        thread,
         thread_local,
         tool_attributes,
         tool_lints,
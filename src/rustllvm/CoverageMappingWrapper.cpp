@@ -0,0 +1,89 @@
+#include "rustllvm.h"
+
+#include "llvm/ADT/ArrayRef.h"
+#include "llvm/ProfileData/Coverage/CoverageMapping.h"
+#include "llvm/ProfileData/Coverage/CoverageMappingWriter.h"
+#include "llvm/ProfileData/InstrProf.h"
+
+using namespace llvm;
+
+// A region of source code covered by a counter. Mirrors
+// `llvm::ffi::CounterMappingRegion` on the Rust side.
+struct LLVMRustCounterMappingRegion {
+  uint32_t CounterID;
+  uint32_t FileID;
+  uint32_t LineStart;
+  uint32_t ColumnStart;
+  uint32_t LineEnd;
+  uint32_t ColumnEnd;
+};
+
+extern "C" void LLVMRustCoverageWriteFilenamesSectionToBuffer(
+    const char *const Filenames[], size_t FilenamesLen,
+    RustStringRef BufferOut) {
+  SmallVector<StringRef, 32> FilenameRefs;
+  for (size_t I = 0; I < FilenamesLen; I++) {
+    FilenameRefs.push_back(StringRef(Filenames[I]));
+  }
+  auto FilenamesWriter =
+      coverage::CoverageFilenamesSectionWriter(makeArrayRef(FilenameRefs));
+  RawRustStringOstream OS(BufferOut);
+  FilenamesWriter.write(OS);
+}
+
+extern "C" void LLVMRustCoverageWriteMappingToBuffer(
+    const unsigned *VirtualFileMappingIDs, unsigned NumVirtualFileMappingIDs,
+    const LLVMRustCounterMappingRegion *Regions, unsigned NumRegions,
+    RustStringRef BufferOut) {
+  SmallVector<coverage::CounterMappingRegion, 32> MappingRegions;
+  for (unsigned I = 0; I < NumRegions; I++) {
+    const LLVMRustCounterMappingRegion &Region = Regions[I];
+    MappingRegions.push_back(coverage::CounterMappingRegion::makeRegion(
+        coverage::Counter::getCounter(Region.CounterID), Region.FileID,
+        Region.LineStart, Region.ColumnStart, Region.LineEnd,
+        Region.ColumnEnd));
+  }
+  // Every counter has its own region, so no counter expressions are needed.
+  auto CoverageMappingWriter = coverage::CoverageMappingWriter(
+      makeArrayRef(VirtualFileMappingIDs, NumVirtualFileMappingIDs),
+      ArrayRef<coverage::CounterExpression>(), MappingRegions);
+  RawRustStringOstream OS(BufferOut);
+  CoverageMappingWriter.write(OS);
+}
+
+extern "C" LLVMValueRef LLVMRustCoverageCreatePGOFuncNameVar(LLVMValueRef F,
+                                                             const char *FuncName) {
+  StringRef FuncNameRef(FuncName);
+  return wrap(createPGOFuncNameVar(*cast<Function>(unwrap(F)), FuncNameRef));
+}
+
+extern "C" uint64_t LLVMRustCoverageComputeHash(const char *Name) {
+  StringRef NameRef(Name);
+  return IndexedInstrProf::ComputeHash(NameRef);
+}
+
+extern "C" void LLVMRustCoverageWriteSectionNameToString(LLVMModuleRef M,
+                                                         RustStringRef Str) {
+  Triple TargetTriple(unwrap(M)->getTargetTriple());
+  auto Name = getInstrProfSectionName(IPSK_covmap,
+                                      TargetTriple.getObjectFormat());
+  RawRustStringOstream OS(Str);
+  OS << Name;
+}
+
+extern "C" void LLVMRustCoverageWriteMappingVarNameToString(RustStringRef Str) {
+  auto Name = getCoverageMappingVarName();
+  RawRustStringOstream OS(Str);
+  OS << Name;
+}
+
+// The coverage map written by `librustc_codegen_llvm/coverageinfo` has the
+// layout of the versions of the format before the function records moved to
+// their own section in LLVM 11.
+extern "C" uint32_t LLVMRustCoverageMappingVersion() {
+#if LLVM_VERSION_GE(11, 0)
+  report_fatal_error("`-Z instrument-coverage` does not support LLVM 11 yet");
+#else
+  return coverage::CovMapVersion::CurrentVersion;
+#endif
+}
@@ -6,6 +6,7 @@
 #include "llvm/IR/GlobalVariable.h"
 #include "llvm/IR/Instructions.h"
 #include "llvm/Object/Archive.h"
+#include "llvm/Object/ELFObjectFile.h"
 #include "llvm/Object/ObjectFile.h"
 #include "llvm/Bitcode/BitcodeWriterPass.h"
 #include "llvm/Support/Signals.h"
@@ -201,6 +202,8 @@ static Attribute::AttrKind fromRust(LLVMRustAttribute Kind) {
     return Attribute::OptimizeNone;
   case ReturnsTwice:
     return Attribute::ReturnsTwice;
+  case SanitizeHWAddress:
+    return Attribute::SanitizeHWAddress;
   }
   report_fatal_error("bad AttributeKind");
 }
@@ -1005,6 +1008,52 @@ extern "C" size_t LLVMRustGetSectionName(LLVMSectionIteratorRef SI,
   return Ret.size();
 }
 
+extern "C" uint64_t LLVMRustGetSectionIndex(LLVMSectionIteratorRef SI) {
+  return (*unwrap(SI))->getIndex();
+}
+
+// Returns the index of the section that the relocations in the given section
+// apply to, or 0 (the index of the null section) if it holds no relocations.
+extern "C" uint64_t
+LLVMRustGetRelocatedSectionIndex(LLVMSectionIteratorRef SI) {
+  const SectionRef &Section = **unwrap(SI);
+  section_iterator Relocated = Section.getRelocatedSection();
+  if (Relocated == Section.getObject()->section_end())
+    return 0;
+  return Relocated->getIndex();
+}
+
+inline relocation_iterator *unwrap(LLVMRelocationIteratorRef RI) {
+  return reinterpret_cast<relocation_iterator *>(RI);
+}
+
+// Returns the explicit addend of an ELF relocation, or 0 if the relocation
+// stores its addend in the relocated section instead.
+extern "C" int64_t LLVMRustGetRelocationAddend(LLVMRelocationIteratorRef RI) {
+  const RelocationRef &Relocation = **unwrap(RI);
+  if (!isa<ELFObjectFileBase>(Relocation.getObject()))
+    return 0;
+  Expected<int64_t> Addend = ELFRelocationRef(Relocation).getAddend();
+  if (!Addend) {
+    consumeError(Addend.takeError());
+    return 0;
+  }
+  return *Addend;
+}
+
+inline symbol_iterator *unwrap(LLVMSymbolIteratorRef SI) {
+  return reinterpret_cast<symbol_iterator *>(SI);
+}
+
+extern "C" bool LLVMRustIsFunctionSymbol(LLVMSymbolIteratorRef SI) {
+  Expected<SymbolRef::Type> Type = (*unwrap(SI))->getType();
+  if (!Type) {
+    consumeError(Type.takeError());
+    return false;
+  }
+  return *Type == SymbolRef::ST_Function;
+}
+
 // LLVMArrayType function does not support 64-bit ElementCount
 extern "C" LLVMTypeRef LLVMRustArrayType(LLVMTypeRef ElementTy,
                                          uint64_t ElementCount) {
@@ -1302,6 +1351,13 @@ extern "C" void LLVMRustUnsetComdat(LLVMValueRef V) {
   GV->setComdat(nullptr);
 }
 
+extern "C" void LLVMRustAddTypeMetadata(LLVMValueRef V, uint64_t Offset,
+                                        const char *TypeId, size_t TypeIdLen) {
+  GlobalObject *GV = unwrap<GlobalObject>(V);
+  StringRef TypeIdRef(TypeId, TypeIdLen);
+  GV->addTypeMetadata(Offset, MDString::get(GV->getContext(), TypeIdRef));
+}
+
 enum class LLVMRustLinkage {
   ExternalLinkage = 0,
   AvailableExternallyLinkage = 1,
@@ -82,6 +82,7 @@ enum LLVMRustAttribute {
   NonLazyBind = 23,
   OptimizeNone = 24,
   ReturnsTwice = 25,
+  SanitizeHWAddress = 26,
 };
 
 typedef struct OpaqueRustString *RustStringRef;
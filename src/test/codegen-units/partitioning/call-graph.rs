@@ -0,0 +1,45 @@
+// ignore-tidy-linelength
+// compile-flags:-Zprint-mono-items=lazy -Zcgu-partitioning=callgraph -Ccodegen-units=2
+
+// Merging the two smallest modules would put `a` and `c` together, away from
+// the modules they call into. Merging along the call graph keeps callers and
+// callees in the same codegen unit.
+
+#![allow(dead_code)]
+#![crate_type="lib"]
+
+pub mod a {
+    //~ MONO_ITEM fn call_graph::a[0]::a1[0] @@ call_graph-cgu.1[External]
+    pub fn a1() {
+        super::b::b1();
+    }
+}
+
+pub mod b {
+    //~ MONO_ITEM fn call_graph::b[0]::b1[0] @@ call_graph-cgu.1[External]
+    pub fn b1() -> [u32; 4] {
+        let mut x = [0; 4];
+        x[0] = 1;
+        x[1] = 2;
+        x[2] = 3;
+        x
+    }
+}
+
+pub mod c {
+    //~ MONO_ITEM fn call_graph::c[0]::c1[0] @@ call_graph-cgu.0[External]
+    pub fn c1() {
+        super::d::d1();
+    }
+}
+
+pub mod d {
+    //~ MONO_ITEM fn call_graph::d[0]::d1[0] @@ call_graph-cgu.0[External]
+    pub fn d1() -> [u32; 4] {
+        let mut x = [0; 4];
+        x[0] = 1;
+        x[1] = 2;
+        x[2] = 3;
+        x
+    }
+}
@@ -0,0 +1,41 @@
+// compile-flags:-Zprint-mono-items=eager -Zpolymorphize
+
+#![deny(dead_code)]
+#![feature(start)]
+
+// `T` is never used, all instantiations share a single copy.
+fn unused<T>() -> u32 {
+    42
+}
+
+// Only `U` is unused.
+fn partially_used<T, U>(t: T) -> T {
+    t
+}
+
+// `T` is only used to instantiate `unused`, which is enough to keep it.
+fn used_by_callee<T>() -> u32 {
+    unused::<T>()
+}
+
+//~ MONO_ITEM fn unused_params::start[0]
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    //~ MONO_ITEM fn unused_params::unused[0]<T>
+    let _ = unused::<u8>();
+    let _ = unused::<u16>();
+    let _ = unused::<char>();
+
+    //~ MONO_ITEM fn unused_params::partially_used[0]<u32, U>
+    let _ = partially_used::<u32, u8>(1);
+    let _ = partially_used::<u32, &str>(2);
+    //~ MONO_ITEM fn unused_params::partially_used[0]<char, U>
+    let _ = partially_used::<char, ()>('c');
+
+    //~ MONO_ITEM fn unused_params::used_by_callee[0]<u8>
+    let _ = used_by_callee::<u8>();
+    //~ MONO_ITEM fn unused_params::used_by_callee[0]<u16>
+    let _ = used_by_callee::<u16>();
+
+    0
+}
@@ -0,0 +1,33 @@
+// Verify the debuginfo of generators:
+//  - Each state of the generator points to the line it resumes at
+//  - The saved locals are named after their variables
+
+// ignore-tidy-linelength
+// ignore-windows
+// min-system-llvm-version 8.0
+// compile-flags: -C debuginfo=2 -C no-prepopulate-passes
+
+#![feature(generators, generator_trait)]
+
+use std::ops::Generator;
+
+fn generator_test(x: u32) -> impl Generator<Yield = u32, Return = ()> {
+    move || {
+        yield x;
+        let s = String::from("foo");
+        yield x + 1;
+        drop(s);
+    }
+}
+
+pub fn main() {
+    let _generator = generator_test(1);
+}
+
+// CHECK-DAG: !DIDerivedType(tag: DW_TAG_member, name: "0", scope: {{![0-9]+}}, file: {{![0-9]+}}, line: 15,
+// CHECK-DAG: !DIDerivedType(tag: DW_TAG_member, name: "1", scope: {{![0-9]+}}, file: {{![0-9]+}}, line: 20,
+// CHECK-DAG: !DIDerivedType(tag: DW_TAG_member, name: "2", scope: {{![0-9]+}}, file: {{![0-9]+}}, line: 20,
+// CHECK-DAG: !DIDerivedType(tag: DW_TAG_member, name: "3", scope: {{![0-9]+}}, file: {{![0-9]+}}, line: 16,
+// CHECK-DAG: !DIDerivedType(tag: DW_TAG_member, name: "4", scope: {{![0-9]+}}, file: {{![0-9]+}}, line: 18,
+// CHECK-DAG: !DIDerivedType(tag: DW_TAG_member, name: "__0", scope: {{![0-9]+}}, file: {{![0-9]+}}, baseType:
+// CHECK-DAG: !DIDerivedType(tag: DW_TAG_member, name: "s", scope: {{![0-9]+}}, file: {{![0-9]+}}, baseType:
@@ -0,0 +1,22 @@
+// Test that `-Z instrument-coverage` counts the executions of functions with
+// the profiler runtime, and emits a coverage map for them.
+// Compiling with `-Cpanic=abort` because PGO+unwinding isn't supported on all platforms.
+
+// needs-profiler-support
+// only-linux
+// compile-flags: -Zinstrument-coverage -Ccodegen-units=1 -Cpanic=abort
+
+// CHECK-DAG: @__profc_{{.*}}instrument_coverage{{.*}}some_function{{.*}} = {{.*}}global
+// CHECK-DAG: @__profc_{{.*}}instrument_coverage{{.*}}some_other_function{{.*}} = {{.*}}global
+// CHECK-DAG: @__llvm_coverage_mapping = private constant {{.*}}, section "__llvm_covmap", align 8
+
+#![crate_type="lib"]
+
+#[inline(never)]
+fn some_function() {
+
+}
+
+pub fn some_other_function() {
+    some_function();
+}
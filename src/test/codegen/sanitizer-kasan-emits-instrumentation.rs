@@ -0,0 +1,24 @@
+// Verifies that `-Zsanitizer=kernel-address` emits the instrumentation of the
+// kernel address sanitizer, which always reports errors without aborting.
+//
+// only-x86_64
+// compile-flags: -Zsanitizer=kernel-address --target x86_64-linux-kernel -Copt-level=0
+
+#![feature(no_core, lang_items)]
+#![crate_type = "rlib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+impl Copy for i32 {}
+
+// CHECK-LABEL: define i32 @penguin(
+#[no_mangle]
+pub fn penguin(p: &mut i32) -> i32 {
+    // CHECK:     call void @__asan_report_load4_noabort(
+    // CHECK-NOT: unreachable
+    *p
+}
@@ -0,0 +1,18 @@
+import gdb
+
+
+class PointPrinter(object):
+    def __init__(self, val):
+        self.val = val
+
+    def to_string(self):
+        return "Point(%d, %d)" % (int(self.val["x"]), int(self.val["y"]))
+
+
+def lookup(val):
+    if val.type.strip_typedefs().tag == "debugger_visualizer::Point":
+        return PointPrinter(val)
+    return None
+
+
+gdb.current_objfile().pretty_printers.append(lookup)
@@ -0,0 +1,11 @@
+#![crate_type = "rlib"]
+#![feature(debugger_visualizer)]
+#![debugger_visualizer(gdb_script = "debugger_visualizer.py")]
+
+// no-prefer-dynamic
+// compile-flags:-g
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
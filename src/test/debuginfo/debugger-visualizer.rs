@@ -0,0 +1,30 @@
+// Checks that gdb runs the scripts upstream crates embed with
+// `#[debugger_visualizer]`.
+
+// ignore-lldb
+// ignore-windows
+// ignore-macos
+
+// aux-build:debugger_visualizer.rs
+// compile-flags:-g
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command:info auto-load python-scripts
+// gdb-check:[...]debugger_visualizer::debugger_visualizer.py
+
+// gdb-command:run
+
+// gdb-command:print point
+// gdb-check:$1 = Point(3, -4)
+
+extern crate debugger_visualizer;
+
+use debugger_visualizer::Point;
+
+fn main() {
+    let point = Point { x: 3, y: -4 };
+    zzz(); // #break
+}
+
+fn zzz() { () }
@@ -0,0 +1,66 @@
+// ignore-tidy-linelength
+// ignore-freebsd: gdb package too new
+// ignore-android: FIXME(#10381)
+// compile-flags:-g
+// min-gdb-version 7.7
+// min-lldb-version: 310
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command: run
+
+// gdb-command: print hash_set
+// gdb-check:$1 = HashSet<u64, [...]>(len: 15) = {[...]}
+
+// gdb-command: print hash_map
+// gdb-check:$2 = HashMap<u64, u64, [...]>(len: 15) = {[...]}
+
+// gdb-command: print single_hash_set
+// gdb-check:$3 = HashSet<u64, [...]>(len: 1) = {7}
+
+// gdb-command: print single_hash_map
+// gdb-check:$4 = HashMap<u64, u64, [...]>(len: 1) = {[7] = 42}
+
+// gdb-command: print empty_hash_map
+// gdb-check:$5 = HashMap<u64, u64, [...]>(len: 0)
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command: run
+
+// lldb-command: print single_hash_set
+// lldb-check:[...]$0 = {7}
+
+// lldb-command: print single_hash_map
+// lldb-check:[...]$1 = {7: 42}
+
+// lldb-command: print empty_hash_map
+// lldb-check:[...]$2 = {}
+
+#![allow(unused_variables)]
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+fn main() {
+    let mut hash_set = HashSet::new();
+    for i in 0..15 {
+        hash_set.insert(i as u64);
+    }
+
+    let mut hash_map = HashMap::new();
+    for i in 0..15 {
+        hash_map.insert(i as u64, i as u64);
+    }
+
+    let mut single_hash_set = HashSet::new();
+    single_hash_set.insert(7u64);
+
+    let mut single_hash_map = HashMap::new();
+    single_hash_map.insert(7u64, 42u64);
+
+    let empty_hash_map: HashMap<u64, u64> = HashMap::new();
+
+    zzz(); // #break
+}
+
+fn zzz() { () }
@@ -0,0 +1,75 @@
+// ignore-tidy-linelength
+// ignore-freebsd: gdb package too new
+// ignore-android: FIXME(#10381)
+// compile-flags:-g
+// min-gdb-version 7.7
+// min-lldb-version: 310
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command: run
+
+// gdb-command: print rc
+// gdb-check:$1 = Rc<i32>(strong: 2, weak: 1) = {value = 7}
+
+// gdb-command: print arc
+// gdb-check:$2 = Arc<i32>(strong: 1, weak: 0) = {value = 8}
+
+// gdb-command: print cell
+// gdb-check:$3 = Cell<i32> = {value = 9}
+
+// gdb-command: print ref_cell
+// gdb-check:$4 = RefCell<i32>(borrow: 1) = {value = 10}
+
+// gdb-command: print borrowed
+// gdb-check:$5 = Ref<i32>(borrow: 1) = {value = 10}
+
+// gdb-command: print mut_ref_cell
+// gdb-check:$6 = RefCell<i32>(borrow_mut: 1) = {value = 11}
+
+// gdb-command: print mut_borrowed
+// gdb-check:$7 = RefMut<i32>(borrow_mut: 1) = {value = 11}
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command: run
+
+// lldb-command: print rc
+// lldb-check:[...]$0 = Rc<i32>(strong: 2, weak: 1) 7
+
+// lldb-command: print arc
+// lldb-check:[...]$1 = Arc<i32>(strong: 1, weak: 0) 8
+
+// lldb-command: print cell
+// lldb-check:[...]$2 = Cell<i32> { value: 9 }
+
+// lldb-command: print ref_cell
+// lldb-check:[...]$3 = RefCell<i32>(borrow: 1) { value: 10 }
+
+// lldb-command: print mut_ref_cell
+// lldb-check:[...]$4 = RefCell<i32>(borrow_mut: 1) { value: 11 }
+
+#![allow(unused_variables)]
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn main() {
+    let rc = Rc::new(7);
+    let rc2 = rc.clone();
+    let rc_weak = Rc::downgrade(&rc);
+
+    let arc = Arc::new(8);
+
+    let cell = Cell::new(9);
+
+    let ref_cell = RefCell::new(10);
+    let borrowed = ref_cell.borrow();
+
+    let mut_ref_cell = RefCell::new(11);
+    let mut_borrowed = mut_ref_cell.borrow_mut();
+
+    zzz(); // #break
+}
+
+fn zzz() { () }
@@ -0,0 +1,52 @@
+// Checks that gdb finds the debuginfo of a binary whose DWARF was split off
+// into `.dwo` files with `-Z split-debuginfo=unpacked`.
+
+// only-linux
+// ignore-lldb
+// min-gdb-version: 8.1
+
+// compile-flags:-g -Ccodegen-units=2 -Z split-debuginfo=unpacked
+
+// === GDB TESTS ===============================================================
+
+// gdb-command:run
+
+// gdb-command:print xxx
+// gdb-check:$1 = 12345
+// gdb-command:print sss
+// gdbr-check:$2 = split_debuginfo_unpacked::a::Struct {x: 1, y: 2.5}
+// gdb-command:continue
+
+// gdb-command:print yyy
+// gdb-check:$3 = 67890
+// gdb-command:continue
+
+#![feature(omit_gdb_pretty_printer_section)]
+#![omit_gdb_pretty_printer_section]
+
+mod a {
+    pub struct Struct {
+        x: i32,
+        y: f64,
+    }
+
+    pub fn foo(xxx: u32) {
+        let sss = Struct { x: 1, y: 2.5 };
+        super::_zzz(); // #break
+        drop(sss);
+    }
+}
+
+mod b {
+    pub fn bar(yyy: u64) {
+        super::_zzz(); // #break
+    }
+}
+
+fn main() {
+    a::foo(12345);
+    b::bar(67890);
+}
+
+#[inline(never)]
+fn _zzz() {}
@@ -0,0 +1,60 @@
+// Test that with `-Z incremental-delta-cgus`, a function that is edited in
+// every session is moved out of its module's codegen unit, so that the rest
+// of the module is reused from the second edit onwards, that it stays in its
+// own codegen unit while it is left unchanged, and that it moves back into
+// its module once the module has to be codegened again anyway.
+
+// revisions: cfail1 cfail2 cfail3 cfail4 cfail5
+// compile-flags: -Z query-dep-graph -Z incremental-delta-cgus
+// build-pass
+
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+#![crate_type = "rlib"]
+
+// The first edit moves `edited` out of `m`, which changes the contents of `m`.
+#![rustc_partition_codegened(module="delta_cgus-m", cfg="cfail2")]
+#![rustc_partition_codegened(module="delta_cgus-m-edited.delta", cfg="cfail2")]
+// While it keeps changing, `edited` lives in a codegen unit of its own.
+#![rustc_partition_reused(module="delta_cgus-m", cfg="cfail3")]
+#![rustc_partition_codegened(module="delta_cgus-m-edited.delta", cfg="cfail3")]
+// Once `edited` is unchanged, both codegen units are reused as they are.
+#![rustc_partition_reused(module="delta_cgus-m", cfg="cfail4")]
+#![rustc_partition_reused(module="delta_cgus-m-edited.delta", cfg="cfail4")]
+// Editing `untouched` moves it out of `m`, so `m` is codegened again and the
+// unchanged `edited` is merged back into it.
+#![rustc_partition_codegened(module="delta_cgus-m", cfg="cfail5")]
+#![rustc_partition_codegened(module="delta_cgus-m-untouched.delta", cfg="cfail5")]
+#![rustc_partition_reused(module="delta_cgus-other", cfg="cfail2")]
+#![rustc_partition_reused(module="delta_cgus-other", cfg="cfail3")]
+#![rustc_partition_reused(module="delta_cgus-other", cfg="cfail4")]
+#![rustc_partition_reused(module="delta_cgus-other", cfg="cfail5")]
+
+pub mod m {
+    pub fn edited() -> u32 {
+        #[cfg(cfail1)]
+        return 1;
+
+        #[cfg(cfail2)]
+        return 2;
+
+        #[cfg(not(any(cfail1, cfail2)))]
+        return 3;
+    }
+
+    #[cfg(not(cfail5))]
+    pub fn untouched(x: u32) -> u32 {
+        x * 2
+    }
+
+    #[cfg(cfail5)]
+    pub fn untouched(x: u32) -> u32 {
+        x * 3
+    }
+}
+
+pub mod other {
+    pub fn untouched() -> u32 {
+        super::m::untouched(21)
+    }
+}
@@ -0,0 +1,8 @@
+#![crate_type = "rlib"]
+#![feature(debugger_visualizer)]
+#![debugger_visualizer(gdb_script = "printer.py")]
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
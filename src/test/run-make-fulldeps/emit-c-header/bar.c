@@ -0,0 +1,10 @@
+#include "foo.h"
+
+int main(void) {
+    Shape shape = { { 0.0, 0.0 } };
+    shape.origin = point_new(1.0, 2.0);
+    Color color = Color_Blue;
+    shape.next = &shape;
+    shape_paint(&shape, color);
+    return (int) shape_area(&shape) + count_things(0, 0);
+}
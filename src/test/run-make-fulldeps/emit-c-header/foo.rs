@@ -0,0 +1,57 @@
+#![crate_type = "staticlib"]
+
+use std::os::raw::c_void;
+
+#[repr(C)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    #[no_mangle]
+    pub extern "C" fn point_new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+}
+
+#[repr(C)]
+pub struct Shape {
+    pub origin: Point,
+    pub corners: [Point; 4],
+    pub next: *mut Shape,
+}
+
+#[repr(u8)]
+pub enum Color {
+    Red,
+    Green = 4,
+    Blue,
+}
+
+#[repr(transparent)]
+pub struct Handle(*mut c_void);
+
+#[no_mangle]
+pub extern "C" fn shape_area(shape: &Shape) -> f64 {
+    let _ = shape;
+    0.0
+}
+
+#[no_mangle]
+pub extern "C" fn shape_paint(shape: *mut Shape, color: Color) {
+    let _ = (shape, color);
+}
+
+#[no_mangle]
+pub extern "C" fn set_callback(handle: Handle, callback: Option<extern "C" fn(u32) -> bool>) {
+    let _ = (handle, callback);
+}
+
+#[export_name = "count_things"]
+pub extern "C" fn count(things: *const *const u8, len: usize) -> i32 {
+    let _ = (things, len);
+    0
+}
+
+pub extern "C" fn not_exported() {}
@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[inline(never)]
+fn count() {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline(never)]
+fn never_called() {
+    println!("never called");
+}
+
+fn main() {
+    for _ in 0..3 {
+        count();
+    }
+
+    if std::env::args().count() > 5 {
+        never_called();
+    }
+}
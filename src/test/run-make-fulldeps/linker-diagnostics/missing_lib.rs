@@ -0,0 +1,10 @@
+#![crate_type = "rlib"]
+
+#[link(name = "missing_native_lib")]
+extern "C" {
+    fn missing_native_fn();
+}
+
+pub fn call() {
+    unsafe { missing_native_fn() }
+}
@@ -0,0 +1,5 @@
+extern crate missing_lib;
+
+fn main() {
+    missing_lib::call();
+}
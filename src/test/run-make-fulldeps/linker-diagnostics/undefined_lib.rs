@@ -0,0 +1,9 @@
+#![crate_type = "rlib"]
+
+extern "C" {
+    fn this_symbol_is_not_defined();
+}
+
+pub fn call() {
+    unsafe { this_symbol_is_not_defined() }
+}
@@ -0,0 +1,5 @@
+extern crate undefined_lib;
+
+fn main() {
+    undefined_lib::call();
+}
@@ -0,0 +1,9 @@
+// The vtable of the error is created by `From<&str> for Box<dyn Error>` in the
+// standard library.
+
+use std::error::Error;
+
+fn main() {
+    let e: Box<dyn Error> = "something went wrong".into();
+    println!("{}", e);
+}
@@ -0,0 +1,68 @@
+use std::env;
+use std::mem;
+
+trait Animal {
+    fn legs(&self) -> u32;
+}
+
+struct Dog;
+
+impl Animal for Dog {
+    fn legs(&self) -> u32 {
+        4
+    }
+}
+
+trait Plant {
+    fn leaves(&self) -> u32;
+}
+
+struct Tree;
+
+impl Plant for Tree {
+    fn leaves(&self) -> u32 {
+        1000
+    }
+}
+
+fn add_one(x: u32) -> u32 {
+    x + 1
+}
+
+fn add_one_wide(x: u64) -> u64 {
+    x + 1
+}
+
+#[inline(never)]
+fn call(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+#[inline(never)]
+fn legs(animal: &dyn Animal) -> u32 {
+    animal.legs()
+}
+
+fn main() {
+    let dog: Box<dyn Animal> = Box::new(Dog);
+    assert_eq!(dog.legs(), 4);
+    assert_eq!(legs(&Dog), 4);
+    assert_eq!(call(add_one, 1), 2);
+    let add_one_closure = |x: u32| x + 1;
+    assert_eq!(call(add_one_closure, 1), 2);
+    println!("valid calls succeeded");
+
+    match &*env::args().nth(1).unwrap() {
+        "fn-ptr" => {
+            let f: fn(u32) -> u32 = unsafe { mem::transmute(add_one_wide as fn(u64) -> u64) };
+            call(f, 1);
+        }
+        "vtable" => {
+            let tree: &dyn Plant = &Tree;
+            let animal: &dyn Animal = unsafe { mem::transmute(tree) };
+            legs(animal);
+        }
+        mismatch => panic!("unknown mismatch `{}`", mismatch),
+    }
+    println!("mismatched call succeeded");
+}
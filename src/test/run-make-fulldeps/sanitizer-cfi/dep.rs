@@ -0,0 +1,21 @@
+#![crate_type = "rlib"]
+
+pub trait Shape {
+    fn area(&self) -> u32;
+}
+
+pub struct Square(pub u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+pub fn square(side: u32) -> Box<dyn Shape> {
+    Box::new(Square(side))
+}
+
+pub fn double(x: u32) -> u32 {
+    x * 2
+}
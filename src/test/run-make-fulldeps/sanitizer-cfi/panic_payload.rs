@@ -0,0 +1,9 @@
+// The payload of the panic is boxed by the panic machinery of the standard
+// library.
+
+use std::thread;
+
+fn main() {
+    let payload = thread::spawn(|| panic!("boom")).join().unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"));
+}
@@ -0,0 +1,12 @@
+extern crate dep;
+
+#[inline(never)]
+fn call(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+fn main() {
+    // Both the vtable and the function are defined in `dep`.
+    assert_eq!(dep::square(3).area(), 9);
+    assert_eq!(call(dep::double, 2), 4);
+}
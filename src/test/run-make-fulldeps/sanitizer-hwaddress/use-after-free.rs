@@ -0,0 +1,8 @@
+use std::ptr;
+
+fn main() {
+    let xs = vec![0, 1, 2, 3];
+    let p = xs.as_ptr();
+    drop(xs);
+    let _y = unsafe { ptr::read_volatile(p) };
+}
@@ -0,0 +1,14 @@
+pub struct SplitDebuginfoStruct {
+    pub x: u32,
+    pub y: f64,
+}
+
+#[inline(never)]
+fn split_debuginfo_marker(s: &SplitDebuginfoStruct) -> f64 {
+    s.x as f64 + s.y
+}
+
+fn main() {
+    let s = SplitDebuginfoStruct { x: 1, y: 2.5 };
+    println!("{}", split_debuginfo_marker(&s));
+}
@@ -0,0 +1,18 @@
+#![crate_type = "lib"]
+
+pub fn leaf(i: usize) -> u8 {
+    let buf = [i as u8; 4096];
+    buf[i % 4096]
+}
+
+pub fn root(i: usize) -> u8 {
+    leaf(i).wrapping_add(1)
+}
+
+pub fn small(i: usize) -> usize {
+    i + 1
+}
+
+pub fn recursive(i: usize) -> usize {
+    if i == 0 { 0 } else { recursive(i - 1) + 1 }
+}
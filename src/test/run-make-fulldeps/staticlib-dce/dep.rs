@@ -0,0 +1,11 @@
+#![crate_type = "rlib"]
+
+#[inline(never)]
+pub fn used_by_foo(x: u32) -> u32 {
+    x.wrapping_mul(3)
+}
+
+#[inline(never)]
+pub fn never_used(x: u32) -> u32 {
+    x.wrapping_mul(5)
+}
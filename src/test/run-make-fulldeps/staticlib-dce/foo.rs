@@ -0,0 +1,8 @@
+#![crate_type = "staticlib"]
+
+extern crate dep;
+
+#[no_mangle]
+pub extern "C" fn foo_exported(x: u32) -> u32 {
+    dep::used_by_foo(x)
+}
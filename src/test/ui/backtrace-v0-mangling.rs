@@ -0,0 +1,85 @@
+// run-pass
+// ignore-android FIXME #17520
+// ignore-cloudabi spawning processes is not supported
+// ignore-emscripten spawning processes is not supported
+// ignore-openbsd no support for libbacktrace without filename
+// ignore-sgx no processes
+// ignore-msvc see #62897 and `backtrace-debuginfo.rs` test
+// compile-flags:-g -Z symbol-mangling-version=v0
+
+// Checks that backtraces demangle symbols of the v0 mangling scheme.
+
+#![allow(incomplete_features)]
+#![feature(const_generics, non_ascii_idents, unsized_locals)]
+
+use std::env;
+use std::process::Command;
+use std::str;
+
+#[inline(never)]
+fn generic<T>() -> T {
+    let f = || -> T { panic!() };
+    f()
+}
+
+#[inline(never)]
+fn const_param<const N: usize>() {
+    panic!()
+}
+
+#[inline(never)]
+fn gödel() {
+    panic!()
+}
+
+trait Consume {
+    fn consume(self);
+}
+
+struct Bomb;
+
+impl Consume for Bomb {
+    #[inline(never)]
+    fn consume(self) {
+        panic!()
+    }
+}
+
+// Runs this test again with `case` as argument and checks that the backtrace
+// printed by its panic contains all of `expected`.
+fn check(case: &str, expected: &[&str]) {
+    let me = env::args().next().unwrap();
+    let out = Command::new(&me).arg(case).env("RUST_BACKTRACE", "1").output().unwrap();
+    assert!(!out.status.success());
+    let s = str::from_utf8(&out.stderr).unwrap();
+    for expected in expected {
+        assert!(s.contains(expected), "`{}` not found in the output: {}", expected, s);
+    }
+    assert!(!s.contains("_RN"), "bad output: {}", s);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() >= 2 {
+        match &*args[1] {
+            "generic" => {
+                generic::<u32>();
+            }
+            "const" => const_param::<3>(),
+            "punycode" => gödel(),
+            // Calling a by-value method on a trait object goes through a
+            // vtable shim.
+            "shim" => {
+                let bomb: Box<dyn Consume> = Box::new(Bomb);
+                bomb.consume();
+            }
+            _ => unreachable!(),
+        }
+        return;
+    }
+
+    check("generic", &[" backtrace_v0_mangling::generic::<u32>", "::{closure#0}"]);
+    check("const", &[" backtrace_v0_mangling::const_param::<3>"]);
+    check("punycode", &[" backtrace_v0_mangling::gödel"]);
+    check("shim", &["Consume>::consume", "::consume::{shim#0}"]);
+}
@@ -0,0 +1,27 @@
+// compile-flags: --emit=c-header --crate-type=lib
+
+#[no_mangle]
+pub extern "C" fn takes_array(_a: [u8; 4]) {}
+//~^ ERROR `takes_array` cannot be declared in a C header
+
+#[no_mangle]
+pub extern "C" fn returns_array() -> [u8; 4] {
+//~^ ERROR `returns_array` cannot be declared in a C header
+    [0; 4]
+}
+
+#[repr(transparent)]
+pub struct Wrapper([u32; 2]);
+
+#[no_mangle]
+pub extern "C" fn takes_wrapper(_w: Wrapper) {}
+//~^ ERROR `takes_wrapper` cannot be declared in a C header
+
+#[repr(C)]
+pub struct WithArray {
+    pub a: [u8; 4],
+}
+
+// Arrays are fine as fields and behind pointers.
+#[no_mangle]
+pub extern "C" fn takes_struct(_s: WithArray, _p: *const [u8; 4]) {}
@@ -0,0 +1,14 @@
+// compile-flags: --emit=c-header --crate-type=lib
+
+#[no_mangle]
+pub extern "C" fn takes_str(_s: &str) {}
+//~^ ERROR `takes_str` cannot be declared in a C header
+
+#[repr(C)]
+pub struct Wrapper {
+    pub c: char,
+}
+
+#[no_mangle]
+pub extern "C" fn takes_wrapper(_w: Wrapper) {}
+//~^ ERROR `takes_wrapper` cannot be declared in a C header
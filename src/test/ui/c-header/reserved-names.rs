@@ -0,0 +1,39 @@
+// compile-flags: --emit=c-header --crate-type=lib
+
+#![allow(non_camel_case_types)]
+
+#[no_mangle]
+pub extern "C" fn register(_x: u32) {}
+//~^ ERROR `register` cannot be declared in a C header
+
+#[no_mangle]
+pub extern "C" fn takes_default(default: u32) -> u32 {
+//~^ ERROR `takes_default` cannot be declared in a C header
+    default
+}
+
+#[export_name = "__init_lib"]
+pub extern "C" fn init_lib() {}
+//~^ ERROR `init_lib` cannot be declared in a C header
+
+#[repr(C)]
+pub struct int {
+    pub x: u32,
+}
+
+#[no_mangle]
+pub extern "C" fn takes_int(_i: *const int) {}
+//~^ ERROR `takes_int` cannot be declared in a C header
+
+#[repr(C)]
+pub struct Token {
+    pub class: u8,
+}
+
+#[no_mangle]
+pub extern "C" fn takes_token(_t: Token) {}
+//~^ ERROR `takes_token` cannot be declared in a C header
+
+// Names that are only reserved at file scope are fine for parameters.
+#[no_mangle]
+pub extern "C" fn takes_underscored(_x: u32, _y: u32) {}
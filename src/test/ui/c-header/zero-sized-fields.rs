@@ -0,0 +1,35 @@
+// compile-flags: --emit=c-header --crate-type=lib
+
+use std::marker::PhantomData;
+
+#[repr(C)]
+pub struct OverAligned {
+    pub a: u8,
+    pub align: [u64; 0],
+    pub b: u8,
+}
+
+#[no_mangle]
+pub extern "C" fn takes_over_aligned(_s: OverAligned) {}
+//~^ ERROR `takes_over_aligned` cannot be declared in a C header
+
+#[repr(C)]
+pub union OverAlignedUnion {
+    pub a: u8,
+    pub align: [u32; 0],
+}
+
+#[no_mangle]
+pub extern "C" fn takes_over_aligned_union(_u: *const OverAlignedUnion) {}
+//~^ ERROR `takes_over_aligned_union` cannot be declared in a C header
+
+// Zero-sized fields that are aligned to a byte don't affect the layout.
+#[repr(C)]
+pub struct WithMarker {
+    pub a: u8,
+    pub marker: PhantomData<u64>,
+    pub unit: (),
+}
+
+#[no_mangle]
+pub extern "C" fn takes_with_marker(_s: WithMarker) {}
@@ -0,0 +1,4 @@
+// compile-flags: -Z cgu-partitioning=random
+// error-pattern: either `size` or `callgraph` was expected
+
+fn main() {}
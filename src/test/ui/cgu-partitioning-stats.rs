@@ -0,0 +1,42 @@
+// Check the number of references between codegen units printed by
+// `-Z cgu-partitioning-stats` for both merging strategies. Merging by size
+// puts `a` and `c` together, leaving one of their calls to `b` and `d` across
+// codegen units, while merging along the call graph keeps each caller with
+// its callee.
+
+// build-pass
+// compile-flags: -Z cgu-partitioning=callgraph -Z cgu-partitioning-stats -C codegen-units=2
+
+#![crate_type = "lib"]
+
+pub mod a {
+    pub fn a1() {
+        super::b::b1();
+    }
+}
+
+pub mod b {
+    pub fn b1() -> [u32; 4] {
+        let mut x = [0; 4];
+        x[0] = 1;
+        x[1] = 2;
+        x[2] = 3;
+        x
+    }
+}
+
+pub mod c {
+    pub fn c1() {
+        super::d::d1();
+    }
+}
+
+pub mod d {
+    pub fn d1() -> [u32; 4] {
+        let mut x = [0; 4];
+        x[0] = 1;
+        x[1] = 2;
+        x[2] = 3;
+        x
+    }
+}
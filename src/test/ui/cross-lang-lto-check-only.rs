@@ -0,0 +1,8 @@
+// Check that `-Z cross-lang-lto` doesn't look for the linker plugin when
+// nothing is linked.
+
+// check-pass
+// only-linux
+// compile-flags: -Z cross-lang-lto
+
+fn main() {}
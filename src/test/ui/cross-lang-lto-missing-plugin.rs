@@ -0,0 +1,10 @@
+// Check that `-Z cross-lang-lto` warns and builds without linker-plugin LTO
+// when the linker plugin is not installed in the sysroot.
+
+// build-pass
+// only-linux
+// compile-flags: -Z cross-lang-lto
+// normalize-stderr-test "LLVMgold-[0-9.]*\.so" -> "LLVMgold-$$LLVM_VERSION.so"
+// normalize-stderr-test " in `[^`]*`" -> " in `$$LIBDIR`"
+
+fn main() {}
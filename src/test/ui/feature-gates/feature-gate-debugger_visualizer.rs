@@ -0,0 +1,4 @@
+#![debugger_visualizer(gdb_script = "foo.py")]
+//~^ ERROR the `#[debugger_visualizer]` attribute is an experimental feature
+
+fn main() {}
@@ -0,0 +1,8 @@
+// normalize-stderr-test: "missing.py:.*\(" -> "missing.py: $$FILE_NOT_FOUND_MSG ("
+// compile-flags: --crate-type=lib
+
+#![feature(debugger_visualizer)]
+#![debugger_visualizer(natvis_file = "foo.natvis")]
+//~^ ERROR invalid argument to `debugger_visualizer`
+#![debugger_visualizer(gdb_script = "missing.py")]
+//~^ ERROR couldn't read
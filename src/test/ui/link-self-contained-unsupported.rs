@@ -0,0 +1,5 @@
+// compile-flags: -Z link-self-contained
+// ignore-musl
+// normalize-stderr-test: "for the `.*` target" -> "for the `$$TARGET` target"
+
+fn main() {}
@@ -0,0 +1,12 @@
+// build-pass
+// compile-flags: -Z mono-item-stats
+
+#![crate_type = "lib"]
+
+fn id<T>(t: T) -> T {
+    t
+}
+
+pub fn instantiate() -> (u8, u16, u32) {
+    (id(1), id(2), id(3))
+}
@@ -0,0 +1,30 @@
+// Checks that `#[no_sanitize]` is only accepted on functions with a body.
+
+#![feature(no_sanitize)]
+
+#[no_sanitize(address)] //~ ERROR attribute should be applied to a function
+static X: u32 = 0;
+
+#[no_sanitize(address)] //~ ERROR attribute should be applied to a function
+struct S;
+
+trait T {
+    #[no_sanitize(address)] //~ ERROR attribute should be applied to a function
+    fn required(&self);
+
+    #[no_sanitize(address)]
+    fn provided(&self) {}
+}
+
+impl T for S {
+    #[no_sanitize(address)]
+    fn required(&self) {}
+}
+
+impl S {
+    #[no_sanitize(address)]
+    fn inherent(&self) {}
+}
+
+#[no_sanitize(address)]
+fn main() {}
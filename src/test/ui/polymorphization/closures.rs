@@ -0,0 +1,64 @@
+// run-pass
+// compile-flags: -Zpolymorphize
+
+// Check that closures which don't use some or all of the generic parameters of
+// their parent still behave correctly once their instances are shared.
+
+use std::fmt::Debug;
+
+// The closure doesn't use `T`.
+fn unused<T>() -> u32 {
+    let add_one = |x: u32| x + 1;
+    add_one(41)
+}
+
+// The closure only uses `T`, not `U`.
+fn partially_used<T: Clone, U>(t: T, _: U) -> (T, T) {
+    let dup = |t: T| (t.clone(), t);
+    dup(t)
+}
+
+// The closure captures a `T` and returns it as a string.
+fn used<T: Debug>(t: T) -> String {
+    let format = move || format!("{:?}", t);
+    format()
+}
+
+// A closure that doesn't use `T` inside one that does.
+fn nested<T: Debug>(t: T) -> (String, u32) {
+    let outer = move || {
+        let inner = |x: u32| x * 2;
+        (format!("{:?}", t), inner(21))
+    };
+    outer()
+}
+
+// The closure is returned, and is called by the instances of the caller.
+fn make_counter<T>() -> impl FnMut() -> u32 {
+    let mut count = 0;
+    move || {
+        count += 1;
+        count
+    }
+}
+
+fn main() {
+    assert_eq!(unused::<u8>(), 42);
+    assert_eq!(unused::<String>(), 42);
+
+    assert_eq!(partially_used(1u8, "a"), (1, 1));
+    assert_eq!(partially_used(1u8, 2u64), (1, 1));
+    assert_eq!(partially_used('c', ()), ('c', 'c'));
+
+    assert_eq!(used(1u8), "1");
+    assert_eq!(used("a"), "\"a\"");
+
+    assert_eq!(nested(Some(1)), ("Some(1)".to_string(), 42));
+    assert_eq!(nested(()), ("()".to_string(), 42));
+
+    let mut a = make_counter::<u8>();
+    let mut b = make_counter::<Vec<String>>();
+    assert_eq!(a(), 1);
+    assert_eq!(a(), 2);
+    assert_eq!(b(), 1);
+}
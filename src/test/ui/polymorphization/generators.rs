@@ -0,0 +1,69 @@
+// run-pass
+// compile-flags: -Zpolymorphize
+
+// Check that generators which don't use some or all of the generic parameters
+// of their parent still behave correctly once their instances are shared.
+
+#![feature(generators, generator_trait)]
+
+use std::marker::Unpin;
+use std::ops::{Generator, GeneratorState};
+use std::pin::Pin;
+
+fn finish<G>(mut g: G) -> Vec<u32>
+where
+    G: Generator<Yield = u32, Return = u32> + Unpin,
+{
+    let mut yielded = Vec::new();
+    loop {
+        match Pin::new(&mut g).resume() {
+            GeneratorState::Yielded(x) => yielded.push(x),
+            GeneratorState::Complete(x) => {
+                yielded.push(x);
+                return yielded;
+            }
+        }
+    }
+}
+
+// The generator doesn't use `T`.
+fn unused<T>() -> impl Generator<Yield = u32, Return = u32> + Unpin {
+    || {
+        yield 1;
+        yield 2;
+        3
+    }
+}
+
+// The generator only uses `T`, not `U`.
+fn partially_used<T: Into<u32>, U>(
+    t: T,
+    _: U,
+) -> impl Generator<Yield = u32, Return = u32> + Unpin {
+    move || {
+        let t = t.into();
+        yield t;
+        t + 1
+    }
+}
+
+// The generator keeps a `T` across a yield point.
+fn used<T: Clone + Into<u32>>(t: T) -> impl Generator<Yield = u32, Return = u32> + Unpin {
+    move || {
+        let copy = t.clone();
+        yield copy.into();
+        t.into()
+    }
+}
+
+fn main() {
+    assert_eq!(finish(unused::<u8>()), [1, 2, 3]);
+    assert_eq!(finish(unused::<String>()), [1, 2, 3]);
+
+    assert_eq!(finish(partially_used(4u8, "a")), [4, 5]);
+    assert_eq!(finish(partially_used(4u8, 2u64)), [4, 5]);
+    assert_eq!(finish(partially_used(6u16, ())), [6, 7]);
+
+    assert_eq!(finish(used(8u8)), [8, 8]);
+    assert_eq!(finish(used('a')), [97, 97]);
+}
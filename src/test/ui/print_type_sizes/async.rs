@@ -0,0 +1,77 @@
+// compile-flags: -Z print-type-sizes
+// edition:2018
+// build-pass
+// ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+// Only keep the generators, the other types depend on the library.
+// normalize-stdout-test "print-type-size type: `[^\[].*\n(print-type-size {2,}.*\n)*" -> ""
+
+// This file illustrates how the saved locals of a generator are laid out.
+// Each optimization is shown next to a generator it doesn't apply to.
+
+#![feature(start)]
+
+pub struct Big([u8; 1024]);
+pub struct Small(u32);
+
+async fn wait() {}
+
+// `arg` is moved from the upvar into a local at the start of the body, and
+// that local is stored in the storage of the upvar rather than next to it.
+async fn big_arg(arg: Big) {
+    wait().await;
+    drop(arg);
+}
+
+// Here a copy of `arg` is saved next to the upvar, which is what `big_arg`
+// looked like when the local was stored separately.
+async fn big_arg_copied(arg: Big) {
+    let copy = Big(arg.0);
+    wait().await;
+    drop(copy);
+}
+
+// `x` and `y` are saved across different suspension points but their storage
+// overlaps, so one of them is stored in the prefix, which is shared by all
+// states. Even though `x` has more conflicts, promoting it would store it
+// next to `z`, so the small `y` is promoted instead and `x` and `z` overlap.
+async fn big_and_small() {
+    let x = Big([0; 1024]);
+    let w1 = Small(1);
+    let w2 = Small(2);
+    wait().await;
+    drop(w1);
+    drop(w2);
+    let y = Small(3);
+    drop(x);
+    let z = Big([0; 1024]);
+    wait().await;
+    drop(y);
+    drop(z);
+}
+
+// Here `x` is still alive when `z` is created, so they can't overlap and `x`
+// is promoted as well.
+async fn big_and_small_kept() {
+    let x = Big([0; 1024]);
+    let w1 = Small(1);
+    let w2 = Small(2);
+    wait().await;
+    drop(w1);
+    drop(w2);
+    let y = Small(3);
+    let z = Big([0; 1024]);
+    drop(x);
+    wait().await;
+    drop(y);
+    drop(z);
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    let _ = big_arg(Big([0; 1024]));
+    let _ = big_arg_copied(Big([0; 1024]));
+    let _ = big_and_small();
+    let _ = big_and_small_kept();
+    0
+}
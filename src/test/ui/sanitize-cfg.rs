@@ -5,8 +5,9 @@
 // only-linux
 // only-x86_64
 // check-pass
-// revisions: address leak memory thread
+// revisions: address cfi leak memory thread
 //[address]compile-flags: -Zsanitizer=address --cfg address
+//[cfi]compile-flags:     -Zsanitizer=cfi     --cfg cfi -Clto
 //[leak]compile-flags:    -Zsanitizer=leak    --cfg leak
 //[memory]compile-flags:  -Zsanitizer=memory  --cfg memory
 //[thread]compile-flags:  -Zsanitizer=thread  --cfg thread
@@ -16,6 +17,9 @@
 #[cfg(all(sanitize = "address", address))]
 fn main() {}
 
+#[cfg(all(sanitize = "cfi", cfi))]
+fn main() {}
+
 #[cfg(all(sanitize = "leak", leak))]
 fn main() {}
 
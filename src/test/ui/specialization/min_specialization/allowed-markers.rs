@@ -0,0 +1,50 @@
+// Check that specializing on marker traits and always applicable traits is
+// allowed.
+
+// check-pass
+
+#![feature(min_specialization)]
+#![feature(rustc_attrs)]
+
+#[rustc_unsafe_specialization_marker]
+trait Marker {}
+
+#[rustc_specialization_trait]
+trait SpecTrait {
+    fn g(&self);
+}
+
+trait X {
+    fn f(&self);
+}
+
+impl<T> X for T {
+    default fn f(&self) {}
+}
+
+impl<T: Marker> X for T {
+    fn f(&self) {}
+}
+
+trait Y {
+    fn h(&self);
+}
+
+impl<T> Y for T {
+    default fn h(&self) {}
+}
+
+impl<T: SpecTrait> Y for T {
+    fn h(&self) {
+        self.g();
+    }
+}
+
+impl SpecTrait for u8 {
+    fn g(&self) {}
+}
+
+fn main() {
+    0u8.f();
+    0u8.h();
+}
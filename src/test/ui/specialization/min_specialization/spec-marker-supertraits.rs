@@ -0,0 +1,13 @@
+// Check that implementations of always applicable traits must themselves be
+// always applicable.
+
+#![feature(min_specialization)]
+#![feature(rustc_attrs)]
+
+#[rustc_specialization_trait]
+trait SpecTrait {}
+
+impl<T: Clone> SpecTrait for T {}
+//~^ ERROR cannot specialize on trait `std::clone::Clone`
+
+fn main() {}
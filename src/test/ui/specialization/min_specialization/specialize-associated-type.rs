@@ -0,0 +1,13 @@
+// Check that `min_specialization` only allows specializing methods.
+
+#![feature(min_specialization)]
+
+trait Tr {
+    type Assoc;
+}
+
+impl<T> Tr for T {
+    default type Assoc = u8; //~ ERROR specialization is unstable
+}
+
+fn main() {}
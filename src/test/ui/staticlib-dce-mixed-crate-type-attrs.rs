@@ -0,0 +1,11 @@
+// Check that `-Z staticlib-dce` warns when `#![crate_type]` attributes ask for
+// other crate types than a static library.
+
+// check-pass
+// compile-flags: -Z staticlib-dce
+
+#![crate_type = "staticlib"]
+#![crate_type = "rlib"]
+
+#[no_mangle]
+pub extern "C" fn foo() {}
@@ -0,0 +1,8 @@
+// Check that `-Z staticlib-dce` warns when the crate isn't only built as a
+// static library, which doesn't enable fat LTO.
+
+// check-pass
+// compile-flags: -Z staticlib-dce --crate-type=staticlib,rlib
+
+#[no_mangle]
+pub extern "C" fn foo() {}
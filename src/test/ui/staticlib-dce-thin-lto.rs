@@ -0,0 +1,9 @@
+// Check that `-Z staticlib-dce` can't be combined with a kind of LTO other
+// than fat LTO.
+
+// compile-flags: -Z staticlib-dce -C lto=thin
+
+#![crate_type = "staticlib"]
+
+#[no_mangle]
+pub extern "C" fn foo() {}
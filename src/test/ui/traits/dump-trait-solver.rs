@@ -0,0 +1,15 @@
+// Check the trace printed by `-Z dump-trait-solver`, including the cause
+// of the rejection of an impl that doesn't match.
+// The filter `u8,` only selects the obligation `(u8, u16): Speak`, which is
+// checked when checking the well-formedness of its impl.
+
+// check-pass
+// compile-flags: -Z dump-trait-solver=u8,
+
+trait Speak {}
+
+impl Speak for (u16, u16) {}
+
+impl Speak for (u8, u16) {}
+
+fn main() {}
@@ -0,0 +1,33 @@
+// Check the notes explaining why a bound deep inside nested impls is required:
+// local impls are pointed at, runs of frames through the same impl are
+// collapsed, and a bound on the caller's type parameter is suggested when the
+// unsatisfied bound is on a concrete type.
+
+trait Foo {}
+
+impl<T: Foo> Foo for Vec<T> {}
+
+struct Local;
+
+fn require_foo<T: Foo>(_: T) {}
+
+fn nested() {
+    let v = vec![vec![vec![vec![Local]]]];
+    require_foo(v);
+    //~^ ERROR the trait bound `Local: Foo` is not satisfied
+}
+
+trait Baz {}
+
+struct Holder<T>(T);
+
+impl<T> Baz for T where Holder<T>: Foo {}
+
+fn require_baz<T: Baz>(_: T) {}
+
+fn generic<T>(x: T) {
+    require_baz(x);
+    //~^ ERROR the trait bound `Holder<T>: Foo` is not satisfied
+}
+
+fn main() {}
@@ -930,6 +930,14 @@ impl<'test> TestCx<'test> {
                             "add-auto-load-safe-path {}\n",
                             rust_pp_module_abs_path.replace(r"\", r"\\")
                         ));
+
+                        // Also allow the inline scripts `#[debugger_visualizer]`
+                        // embeds into the executable itself
+                        let output_base_dir = self.output_base_dir();
+                        script_str.push_str(&format!(
+                            "add-auto-load-safe-path {}\n",
+                            output_base_dir.to_str().unwrap().replace(r"\", r"\\")
+                        ));
                     }
                 }
                 _ => {